@@ -0,0 +1,76 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The canonical answer to a puzzle part, i.e. what gets typed into the answer box
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnswerValue {
+    Int(u128),
+    Text(String),
+}
+
+/// A step-by-step account of a solve, one row per step, e.g. the dial before and
+/// after each of day 1's rotations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trace {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// What a solver returns: the canonical answer plus optional named diagnostics
+/// (intermediate values that are useful to look at but aren't the answer), and
+/// for trace variants the steps that led to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answer {
+    pub value: AnswerValue,
+    pub diagnostics: Vec<(String, String)>,
+    #[serde(default)]
+    pub trace: Option<Trace>,
+}
+
+impl Answer {
+    pub fn int(value: u128) -> Answer {
+        Answer { value: AnswerValue::Int(value), diagnostics: Vec::new(), trace: None }
+    }
+
+    pub fn text(value: impl Into<String>) -> Answer {
+        Answer { value: AnswerValue::Text(value.into()), diagnostics: Vec::new(), trace: None }
+    }
+
+    pub fn with_diagnostic(mut self, name: impl Into<String>, value: impl ToString) -> Answer {
+        self.diagnostics.push((name.into(), value.to_string()));
+        self
+    }
+
+    pub fn with_trace(mut self, trace: Trace) -> Answer {
+        self.trace = Some(trace);
+        self
+    }
+}
+
+impl fmt::Display for AnswerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerValue::Int(value) => write!(f, "{value}"),
+            AnswerValue::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Displays only the canonical value, diagnostics are left to the caller
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// Tab-separated, a header line then one line per row
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.columns.join("\t"))?;
+        for row in self.rows.iter() {
+            writeln!(f, "{}", row.join("\t"))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::days::{catching_panics, find_daypart};
+use crate::error::AocError;
+use crate::params::ParamOverrides;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+impl BenchStats {
+    fn from_samples(samples: &mut [f64]) -> BenchStats {
+        samples.sort_by(f64::total_cmp);
+        let mid = samples.len() / 2;
+        let median_ms = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2.0
+        } else {
+            samples[mid]
+        };
+        BenchStats {
+            runs: samples.len(),
+            min_ms: samples[0],
+            median_ms,
+            max_ms: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchJob {
+    pub day: u64,
+    pub part: u64,
+    pub params: ParamOverrides,
+    pub input: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub day: u64,
+    pub part: u64,
+    pub result: Result<BenchStats, AocError>,
+}
+
+/// Runs the solver `runs` times (at least once) and times parse plus solve,
+/// giving up on the first error, a panic included
+pub fn bench(day: u64, part: u64, input: &str, params: &[(String, String)], runs: usize) -> Result<BenchStats, AocError> {
+    let daypart = find_daypart(day, part).ok_or_else(
+        || AocError::no_solution(format!("No function found for day {day} part {part}"))
+    )?;
+
+    let mut samples = Vec::with_capacity(runs.max(1));
+    for _ in 0..runs.max(1) {
+        let (result, timings) = catching_panics(|| daypart.run(input, params));
+        result?;
+        samples.push(timings.total_ms());
+    }
+    Ok(BenchStats::from_samples(&mut samples))
+}
+
+pub fn bench_all(jobs: &[BenchJob], runs: usize) -> Vec<BenchReport> {
+    jobs.iter().map(|job| {
+        BenchReport { day: job.day, part: job.part, result: bench(job.day, job.part, &job.input, &job.params, runs) }
+    }).collect()
+}
+
+pub fn format_reports(reports: &[BenchReport]) -> String {
+    let mut table = format!("{:>4} {:>5} {:>12} {:>12} {:>12}\n", "day", "part", "min ms", "median ms", "max ms");
+    for report in reports {
+        match &report.result {
+            Ok(stats) => table.push_str(&format!(
+                "{:>4} {:>5} {:>12.3} {:>12.3} {:>12.3}\n",
+                report.day, report.part, stats.min_ms, stats.median_ms, stats.max_ms
+            )),
+            Err(err) => table.push_str(&format!("{:>4} {:>5} {err}\n", report.day, report.part)),
+        }
+    }
+    table
+}
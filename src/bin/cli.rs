@@ -0,0 +1,312 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc2025::answer::Answer;
+use aoc2025::error::AocError;
+use aoc2025::ledger::{self, Ledger, LedgerStatus};
+use aoc2025::params::{self, ParamOverrides};
+use aoc2025::timing::Timings;
+use aoc2025::bench::{self, BenchJob};
+use aoc2025::crosscheck;
+use aoc2025::days::{day_parts, find_daypart, run_jobs, DEFAULT_VARIANT};
+use aoc2025::generate;
+use aoc2025::verify;
+
+const USAGE: &str = "Usage:
+  cli --day <DAY> --part <PART> [FILE]   run one solver on FILE, or stdin if FILE is omitted or -
+  cli --all <DIR>                        run every solver on DIR/day<DAY>.txt
+  cli --verify-examples                  run every solver on the puzzle's example and check the answer
+  cli --generate <SEED> --day <DAY>      print a random input for DAY made from SEED
+
+Options:
+  --variant <NAME>                       run the named variant instead of the default, with --day and --part
+  --param <NAME>=<VALUE>                 override one of the day's parameters, with --day and --part, can be repeated
+  --cross-check                          run every variant of each solver and fail if any disagree
+  --ledger <FILE>                        fail if an answer differs from the one accepted in FILE, a ledger exported from the page
+  --bench <RUNS>                         run each solver RUNS times and report min, median and max times
+  --size <SIZE>                          how big a generated input is, in the day's own units like lines or devices";
+
+enum Target {
+    Single { day: u64, part: u64, file: Option<PathBuf> },
+    All { dir: PathBuf },
+    Examples,
+    Generate { day: u64, seed: u64, size: usize },
+}
+
+struct Options {
+    target: Target,
+    variant: Option<String>,
+    params: ParamOverrides,
+    cross_check: bool,
+    ledger: Option<PathBuf>,
+    bench: Option<usize>,
+}
+
+fn parse_number(flag: &str, value: Option<String>) -> Result<u64, String> {
+    let value = value.ok_or_else(|| format!("{flag} needs a value"))?;
+    str::parse::<u64>(&value).map_err(|_| format!("{flag} expects a number, got {value:?}"))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut all = None;
+    let mut file = None;
+    let mut bench = None;
+    let mut variant = None;
+    let mut params = Vec::new();
+    let mut cross_check = false;
+    let mut ledger = None;
+    let mut examples = false;
+    let mut generate = None;
+    let mut size = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number("--day", args.next())?),
+            "--part" => part = Some(parse_number("--part", args.next())?),
+            "--all" => all = Some(PathBuf::from(args.next().ok_or_else(|| "--all needs a directory".to_string())?)),
+            "--bench" => bench = Some(parse_number("--bench", args.next())?.max(1) as usize),
+            "--variant" => variant = Some(args.next().ok_or_else(|| "--variant needs a name".to_string())?),
+            "--param" => params.push(params::parse_override(&args.next().ok_or_else(|| "--param needs NAME=VALUE".to_string())?)?),
+            "--cross-check" => cross_check = true,
+            "--ledger" => ledger = Some(PathBuf::from(args.next().ok_or_else(|| "--ledger needs a file".to_string())?)),
+            "--verify-examples" => examples = true,
+            "--generate" => generate = Some(parse_number("--generate", args.next())?),
+            "--size" => size = Some(parse_number("--size", args.next())?.max(1) as usize),
+            "-" => file = None,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg}")),
+            _ => {
+                if file.is_some() {
+                    return Err(format!("unexpected argument {arg}"));
+                }
+                file = Some(PathBuf::from(arg));
+            }
+        }
+    }
+
+    if size.is_some() && generate.is_none() {
+        return Err("--size needs --generate".to_string());
+    }
+    let target = match (all, day, part) {
+        _ if generate.is_some() && examples => return Err("--generate cannot be combined with --verify-examples".to_string()),
+        (None, Some(day), None) if let Some(seed) = generate => {
+            if file.is_some() || bench.is_some() || variant.is_some() || !params.is_empty() || cross_check || ledger.is_some() {
+                return Err("--generate only takes --day and --size".to_string());
+            }
+            Target::Generate { day, seed, size: size.unwrap_or(generate::DEFAULT_SIZE) }
+        }
+        _ if generate.is_some() => return Err("--generate needs --day and no --part or --all".to_string()),
+        _ if examples => {
+            if day.is_some() || part.is_some() || file.is_some() || bench.is_some() || variant.is_some() || !params.is_empty() || cross_check || ledger.is_some() {
+                return Err("--verify-examples cannot be combined with other options".to_string());
+            }
+            Target::Examples
+        }
+        (Some(dir), None, None) if file.is_none() => Target::All { dir },
+        (Some(_), _, _) => return Err("--all cannot be combined with --day, --part or FILE".to_string()),
+        (None, Some(day), Some(part)) => Target::Single { day, part, file },
+        (None, _, _) => return Err("--day and --part are both required".to_string()),
+    };
+    if variant.is_some() && !matches!(target, Target::Single { .. }) {
+        return Err("--variant needs --day and --part".to_string());
+    }
+    if !params.is_empty() && !matches!(target, Target::Single { .. }) {
+        return Err("--param needs --day and --part".to_string());
+    }
+    if cross_check && (variant.is_some() || bench.is_some()) {
+        return Err("--cross-check cannot be combined with --variant or --bench".to_string());
+    }
+    if ledger.is_some() && (cross_check || bench.is_some()) {
+        return Err("--ledger cannot be combined with --cross-check or --bench".to_string());
+    }
+    if ledger.is_some() && !params.is_empty() {
+        return Err("--ledger only holds answers for the default parameters".to_string());
+    }
+    if variant.is_some() && bench.is_some() {
+        return Err("--bench always runs the default variant".to_string());
+    }
+    Ok(Options { target, variant, params, cross_check, ledger, bench })
+}
+
+fn read_input(file: &Option<PathBuf>) -> Result<String, String> {
+    match file {
+        Some(path) => std::fs::read_to_string(path).map_err(|err| format!("could not read {}: {err}", path.display())),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).map_err(|err| format!("could not read stdin: {err}"))?;
+            Ok(input)
+        }
+    }
+}
+
+/// Every (day, part, input) the target asks for
+fn collect_jobs(target: &Target, params: &ParamOverrides) -> Result<Vec<BenchJob>, String> {
+    match target {
+        Target::Single { day, part, file } => {
+            find_daypart(*day, *part).ok_or_else(|| format!("No function found for day {day} part {part}"))?;
+            Ok(vec![BenchJob { day: *day, part: *part, params: params.clone(), input: read_input(file)? }])
+        }
+        Target::All { dir } => {
+            let mut jobs = Vec::new();
+            for daypart in day_parts() {
+                let path = dir.join(format!("day{}.txt", daypart.day));
+                if path.is_file() {
+                    jobs.push(BenchJob {
+                        day: daypart.day,
+                        part: daypart.part,
+                        params: params.clone(),
+                        input: read_input(&Some(path))?,
+                    });
+                }
+            }
+            if jobs.is_empty() {
+                return Err(format!("no inputs named day<DAY>.txt found in {}", Path::new(dir).display()));
+            }
+            Ok(jobs)
+        }
+        Target::Examples | Target::Generate { .. } => Ok(Vec::new()),
+    }
+}
+
+/// Diagnostics and traces go to stderr so stdout only carries answers
+fn print_diagnostics(answer: &Answer) {
+    for (name, value) in answer.diagnostics.iter() {
+        eprintln!("  {name}: {value}");
+    }
+    if let Some(trace) = &answer.trace {
+        eprint!("{trace}");
+    }
+}
+
+fn read_ledger(path: &Path) -> Result<Ledger, String> {
+    let json = std::fs::read_to_string(path).map_err(|err| format!("could not read {}: {err}", path.display()))?;
+    Ledger::from_json(&json)
+}
+
+fn run(jobs: &[BenchJob], single: bool, variant: &str, ledger: Option<&Ledger>) -> Result<(), String> {
+    let mut failed = 0;
+    // both parts of a day on the same input share one parse, and a panicking
+    // solver counts as that one failing rather than ending the run
+    for (job, (result, timings)) in jobs.iter().zip(run_jobs(jobs, variant)) {
+        failed += report(job, result, timings, single, ledger)?;
+    }
+
+    if failed > 0 {
+        Err(format!("{failed} of {} solvers failed", jobs.len()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Prints one solver's result, returning how many solvers failed (0 or 1). A
+/// solver which no longer gives its accepted answer counts as failed
+fn report(job: &BenchJob, result: Result<Answer, AocError>, timings: Timings, single: bool, ledger: Option<&Ledger>) -> Result<usize, String> {
+    let timing = format!("parse {:.3} ms, solve {:.3} ms", timings.parse_ms, timings.solve_ms);
+    let answer = match result {
+        Ok(answer) if single => {
+            println!("{answer}");
+            print_diagnostics(&answer);
+            eprintln!("  {timing}");
+            answer
+        }
+        Ok(answer) => {
+            println!("day {} part {}: {answer} ({timing})", job.day, job.part);
+            print_diagnostics(&answer);
+            answer
+        }
+        Err(err) if single => return Err(err.with_context(&job.input)),
+        Err(err) => {
+            println!("day {} part {}: error: {err}", job.day, job.part);
+            return Ok(1);
+        }
+    };
+
+    let Some(ledger) = ledger else {
+        return Ok(0);
+    };
+    let status = ledger.check(job.day, job.part, &ledger::input_hash(&job.input), &answer);
+    eprintln!("  ledger: {}", status.describe());
+    match status {
+        LedgerStatus::Regressed { .. } if single => Err("the answer differs from the one in the ledger".to_string()),
+        LedgerStatus::Regressed { .. } => Ok(1),
+        _ => Ok(0),
+    }
+}
+
+fn run_examples() -> Result<(), String> {
+    let verifications = verify::verify_examples();
+    print!("{}", verify::format_verifications(&verifications));
+
+    if verifications.iter().all(|verification| verification.passed()) {
+        Ok(())
+    } else {
+        Err("some examples failed".to_string())
+    }
+}
+
+fn run_cross_check(jobs: &[BenchJob]) -> Result<(), String> {
+    let mut disagreeing = 0;
+    for job in jobs {
+        let runs = crosscheck::cross_check(job.day, job.part, &job.input, &job.params).map_err(|err| err.to_string())?;
+        println!("day {} part {}:", job.day, job.part);
+        print!("{}", crosscheck::format_cross_check(&runs));
+        if !crosscheck::disagreements(&runs).is_empty() {
+            disagreeing += 1;
+        }
+    }
+
+    if disagreeing > 0 {
+        Err(format!("variants disagree on {disagreeing} of {} solvers", jobs.len()))
+    } else {
+        Ok(())
+    }
+}
+
+fn run_bench(jobs: &[BenchJob], runs: usize) -> Result<(), String> {
+    let reports = bench::bench_all(jobs, runs);
+    print!("{}", bench::format_reports(&reports));
+
+    let failed = reports.iter().filter(|report| report.result.is_err()).count();
+    if failed > 0 {
+        Err(format!("{failed} of {} solvers failed", reports.len()))
+    } else {
+        Ok(())
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {err}");
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let single = matches!(options.target, Target::Single { .. });
+    let result = match options.target {
+        Target::Examples => run_examples(),
+        Target::Generate { day, seed, size } => generate::generate(day, seed, size).map(|input| print!("{input}")).map_err(
+            |err| err.to_string()
+        ),
+        _ => collect_jobs(&options.target, &options.params).and_then(|jobs| match options.bench {
+            _ if options.cross_check => run_cross_check(&jobs),
+            Some(runs) => run_bench(&jobs, runs),
+            None => {
+                let ledger = options.ledger.as_deref().map(read_ledger).transpose()?;
+                run(&jobs, single, options.variant.as_deref().unwrap_or(DEFAULT_VARIANT), ledger.as_ref())
+            }
+        }),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
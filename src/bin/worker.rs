@@ -1,21 +1,33 @@
-use leptos::{view, IntoView};
-use leptos::leptos_dom::error;
-use leptos::prelude::{signal, Action};
-use leptos::wasm_bindgen::JsError;
-use leptos::prelude::*;
+use leptos::logging::error;
+use leptos::wasm_bindgen::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+use web_sys::{DedicatedWorkerGlobalScope, MessageEvent};
 
+use aoc2025::protocol::{self, Request, Response};
 
-fn Worker() -> impl IntoView {
-
-    view! {
-        <div>
-            "test"
-        </div>
+fn post(scope: &DedicatedWorkerGlobalScope, response: &Response) {
+    if let Err(err) = scope.post_message(&JsValue::from_str(&protocol::encode(response))) {
+        error!("Could not post response: {:?}", err);
     }
 }
 
-
 fn main() {
+    console_error_panic_hook::set_once();
+
+    let scope: DedicatedWorkerGlobalScope = web_sys::js_sys::global().unchecked_into();
+    let reply_scope = scope.clone();
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        let request = event.data().as_string().ok_or_else(
+            || "Page sent a message which is not a string".to_string()
+        ).and_then(|data| protocol::decode::<Request>(&data));
+        match request {
+            Ok(request) => post(&reply_scope, &protocol::handle(request)),
+            Err(err) => error!("{}", err),
+        }
+    });
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    // the handler lives as long as the worker
+    onmessage.forget();
 
-    leptos::mount::mount_to_body(Worker)
+    post(&scope, &Response::Ready);
 }
@@ -0,0 +1,65 @@
+use leptos::wasm_bindgen::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+use web_sys::js_sys::Array;
+use web_sys::{Blob, BlobPropertyBag, ErrorEvent, MessageEvent, Url, Worker};
+
+use crate::protocol::{self, Request, Response};
+
+/// The page's handle on the solver worker. Dropping it terminates the worker
+pub struct SolverWorker {
+    worker: Worker,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    _onerror: Closure<dyn FnMut(ErrorEvent)>,
+}
+
+impl SolverWorker {
+    /// Starts the `worker` bin. `on_response` gets every decoded reply, `on_error` gets
+    /// anything the worker throws, including solver panics
+    pub fn spawn(
+        on_response: impl Fn(Response) + 'static,
+        on_error: impl Fn(String) + 'static,
+    ) -> Result<SolverWorker, JsValue> {
+        let origin = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?.location().origin()?;
+
+        // Trunk builds workers with --target no-modules, so the worker is started
+        // from a small script which loads the generated glue and then the wasm
+        let script = Array::new();
+        script.push(&format!(r#"importScripts("{origin}/worker.js");wasm_bindgen("{origin}/worker_bg.wasm");"#).into());
+        let options = BlobPropertyBag::new();
+        options.set_type("text/javascript");
+        let blob = Blob::new_with_str_sequence_and_options(&script, &options)?;
+        let url = Url::create_object_url_with_blob(&blob)?;
+        let worker = Worker::new(&url)?;
+
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let decoded = event.data().as_string().ok_or_else(
+                || "Worker sent a message which is not a string".to_string()
+            ).and_then(|data| protocol::decode::<Response>(&data));
+            match decoded {
+                Ok(response) => on_response(response),
+                Err(err) => leptos::logging::error!("{}", err),
+            }
+        });
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        let onerror = Closure::<dyn FnMut(ErrorEvent)>::new(move |event: ErrorEvent| {
+            on_error(event.message());
+        });
+        worker.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        Ok(SolverWorker { worker, _onmessage: onmessage, _onerror: onerror })
+    }
+
+    pub fn send(&self, request: &Request) -> Result<(), JsValue> {
+        self.worker.post_message(&JsValue::from_str(&protocol::encode(request)))
+    }
+}
+
+impl Drop for SolverWorker {
+    fn drop(&mut self) {
+        // detach the handlers first, the closures are freed along with self
+        self.worker.set_onmessage(None);
+        self.worker.set_onerror(None);
+        self.worker.terminate();
+    }
+}
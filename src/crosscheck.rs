@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::answer::{Answer, AnswerValue};
+use crate::days::{catching_panics, find_daypart};
+use crate::error::AocError;
+use crate::timing::Timings;
+
+/// The outcome of running one variant of a part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantRun {
+    pub variant: String,
+    pub result: Result<Answer, AocError>,
+    pub timings: Timings,
+}
+
+impl VariantRun {
+    fn outcome(&self) -> Result<&AnswerValue, &AocError> {
+        self.result.as_ref().map(|answer| &answer.value)
+    }
+}
+
+/// Runs every variant of a part on the same input, the default first. A variant
+/// that panics comes back as that variant's error
+pub fn cross_check(day: u64, part: u64, input: &str, params: &[(String, String)]) -> Result<Vec<VariantRun>, AocError> {
+    let daypart = find_daypart(day, part).ok_or_else(
+        || AocError::no_solution(format!("No function found for day {day} part {part}"))
+    )?;
+
+    Ok(daypart.variants().into_iter().map(|variant| {
+        let (result, timings) = catching_panics(|| daypart.run_variant(input, variant, params));
+        VariantRun { variant: variant.to_string(), result, timings }
+    }).collect())
+}
+
+/// Variants whose answer (or error) differs from the default's
+pub fn disagreements(runs: &[VariantRun]) -> Vec<&VariantRun> {
+    match runs.split_first() {
+        Some((default, others)) => others.iter().filter(|run| run.outcome() != default.outcome()).collect(),
+        None => Vec::new(),
+    }
+}
+
+pub fn format_cross_check(runs: &[VariantRun]) -> String {
+    let disagree = disagreements(runs);
+    let mut text = String::new();
+    for run in runs {
+        let outcome = match &run.result {
+            Ok(answer) => answer.to_string(),
+            Err(err) => format!("error: {err}"),
+        };
+        let flag = if disagree.iter().any(|other| other.variant == run.variant) { "  DISAGREES" } else { "" };
+        text.push_str(&format!("{}: {outcome} ({:.3} ms){flag}\n", run.variant, run.timings.total_ms()));
+    }
+    if runs.len() < 2 {
+        text.push_str("only one variant, nothing to compare\n");
+    } else if disagree.is_empty() {
+        text.push_str(&format!("all {} variants agree\n", runs.len()));
+    } else {
+        text.push_str(&format!("{} of {} variants disagree with the default\n", disagree.len(), runs.len()));
+    }
+    text
+}
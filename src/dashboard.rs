@@ -0,0 +1,58 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::bench::BenchJob;
+use crate::days::{self, DEFAULT_VARIANT};
+use crate::error::AocError;
+use crate::ledger::LedgerStatus;
+use crate::timing::Timings;
+
+/// One day and part's outcome in a run of everything
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardRow {
+    pub day: u64,
+    pub part: u64,
+    pub result: Result<Answer, AocError>,
+    pub timings: Timings,
+}
+
+/// Runs every job, parsing once where both parts of a day share an input
+pub fn run_all(jobs: &[BenchJob]) -> Vec<DashboardRow> {
+    jobs.iter().zip(days::run_jobs(jobs, DEFAULT_VARIANT)).map(|(job, (result, timings))| {
+        DashboardRow { day: job.day, part: job.part, result, timings }
+    }).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowStatus {
+    /// Solved, with no accepted answer to compare against
+    Ok,
+    Accepted,
+    Mismatch { accepted: String },
+    Error(String),
+}
+
+impl RowStatus {
+    /// `ledger` is the row's ledger check, None if it wasn't checked
+    pub fn of(row: &DashboardRow, ledger: Option<LedgerStatus>) -> RowStatus {
+        match (&row.result, ledger) {
+            (Err(err), _) => RowStatus::Error(err.to_string()),
+            (Ok(_), Some(LedgerStatus::Matches)) => RowStatus::Accepted,
+            (Ok(_), Some(LedgerStatus::Regressed { accepted })) => RowStatus::Mismatch { accepted },
+            (Ok(_), _) => RowStatus::Ok,
+        }
+    }
+}
+
+impl fmt::Display for RowStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowStatus::Ok => write!(f, "ok"),
+            RowStatus::Accepted => write!(f, "ok, matches accepted"),
+            RowStatus::Mismatch { accepted } => write!(f, "mismatch, accepted {accepted}"),
+            RowStatus::Error(message) => write!(f, "error: {message}"),
+        }
+    }
+}
@@ -1,60 +1,186 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::{Answer, Trace};
+use crate::dial::{self, Dial, Instructions};
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::params::{Param, ParamKind, Params};
+use crate::days::{Example, Solution, Variant};
 
-pub fn day1_part1(input: &str) -> Result<String, JsError> {
-    let mut start: i64 = 50;
-    let mut zeros: i64 = 0;
-    for line in input.lines() {
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
 
-        let numstr: String = line.chars().skip(1).collect();
-        let num = str::parse::<i64>(numstr.as_str())?;
-        if line.starts_with("L") {
-            start -= num;
-        } else {
-            start += num;
-        }
+/// A dial for each one the instructions turn, all with the `positions` and `start`
+/// parameters unless `dials` gives them rings of their own
+fn read_dials(instructions: &Instructions, params: &Params) -> Result<Vec<Dial>, AocError> {
+    dial::setup(&instructions.dials, params.int("positions")?, params.int("start")?, params.text("dials")?)
+}
 
-        while start < 0 {
-            start += 100;
-        }
-        while start >= 100 {
-            start -= 100;
+/// Where each dial ended up, as "end" for the puzzle's single dial or "<name> end"
+/// for each of several
+fn with_ends(answer: Answer, dials: &[Dial]) -> Answer {
+    match dials {
+        [dial] => answer.with_diagnostic("end", dial.position),
+        _ => dials.iter().fold(answer, |answer, dial| answer.with_diagnostic(format!("{} end", dial.name), dial.position)),
+    }
+}
+
+/// Runs every instruction, answering with `count` (one of the two things a dial
+/// counts) summed over the dials. Both counts are reported either way, per dial
+/// when there are several
+fn run_dials(instructions: &Instructions, params: &Params, count: fn(&Dial) -> u64) -> Result<Answer, AocError> {
+    let mut dials = read_dials(instructions, params)?;
+    for turn in instructions.turns.iter() {
+        dials[turn.dial].turn(turn.clicks);
+    }
+
+    let answer = Answer::int(dials.iter().map(|dial| count(dial) as u128).sum());
+    let answer = match dials.as_slice() {
+        [dial] => answer.with_diagnostic("ends on 0", dial.ends_on_zero).with_diagnostic("passes 0", dial.passes_zero),
+        _ => dials.iter().fold(answer, |answer, dial| {
+            answer
+                .with_diagnostic(format!("{} ends on 0", dial.name), dial.ends_on_zero)
+                .with_diagnostic(format!("{} passes 0", dial.name), dial.passes_zero)
+        }),
+    };
+    Ok(with_ends(answer, &dials))
+}
+
+/// Turns the dials one click at a time, only checking for 0 once each rotation is done
+fn part1_reference(instructions: &Instructions, params: &Params) -> Result<Answer, AocError> {
+    let mut dials = read_dials(instructions, params)?;
+    let mut zeros: u128 = 0;
+    for turn in instructions.turns.iter() {
+        let dial = &mut dials[turn.dial];
+        for _ in 0..turn.clicks.abs() {
+            dial.position = (dial.position + turn.clicks.signum()).rem_euclid(dial.positions);
         }
-        if start == 0 {
+        if dial.position == 0 {
             zeros += 1;
         }
     }
-    Ok(format!("end: {start}, zeros: {zeros}"))
+    Ok(with_ends(Answer::int(zeros), &dials))
 }
 
-
-pub fn day1_part2(input: &str) -> Result<String, JsError> {
-    let mut start: i64 = 50;
+/// Turns the dials one click at a time, checking for 0 after every click
+fn part2_stepping(instructions: &Instructions, params: &Params) -> Result<Answer, AocError> {
+    let mut dials = read_dials(instructions, params)?;
     let mut zeros: i64 = 0;
-    for line in input.lines() {
-
-        let numstr: String = line.chars().skip(1).collect();
-        let num = str::parse::<i64>(numstr.as_str())?;
-        if line.starts_with("L") {
-            for i in 0..num {
-                start -= 1;
-                if start < 0 {
-                    start += 100;
+    for turn in instructions.turns.iter() {
+        let dial = &mut dials[turn.dial];
+        if turn.clicks < 0 {
+            for _ in 0..-turn.clicks {
+                dial.position -= 1;
+                if dial.position < 0 {
+                    dial.position += dial.positions;
                 }
-                if start == 0 {
+                if dial.position == 0 {
                     zeros += 1;
                 }
             }
         } else {
-            for i in 0..num {
-                start += 1;
-                if start >= 100 {
-                    start -= 100;
+            for _ in 0..turn.clicks {
+                dial.position += 1;
+                if dial.position >= dial.positions {
+                    dial.position -= dial.positions;
                 }
-                if start == 0 {
+                if dial.position == 0 {
                     zeros += 1;
                 }
             }
         }
     }
-    Ok(format!("end: {start}, zeros: {zeros}"))
-}
\ No newline at end of file
+    Ok(with_ends(Answer::int(zeros as u128), &dials))
+}
+
+/// Solves a part the same way as its default, but also returns one row per
+/// rotation with the dial before and after it and how many 0s it counted for,
+/// as `zeros` decides for the part
+fn trace(instructions: &Instructions, params: &Params, zeros: fn(&Dial, i64) -> u64) -> Result<Answer, AocError> {
+    let mut dials = read_dials(instructions, params)?;
+    let mut total: u128 = 0;
+    let mut rows = Vec::with_capacity(instructions.turns.len());
+    for (idx, turn) in instructions.turns.iter().enumerate() {
+        let dial = &mut dials[turn.dial];
+        let before = dial.position;
+        let hits = zeros(dial, turn.clicks);
+        dial.turn(turn.clicks);
+        total += hits as u128;
+        let direction = if turn.clicks < 0 { 'L' } else { 'R' };
+        rows.push(vec![
+            (idx + 1).to_string(),
+            dial.name.clone(),
+            format!("{direction}{}", turn.clicks.abs()),
+            before.to_string(),
+            dial.position.to_string(),
+            hits.to_string(),
+        ]);
+    }
+    let columns = ["line", "dial", "rotation", "before", "after", "zeros"].map(String::from).to_vec();
+    Ok(with_ends(Answer::int(total), &dials).with_trace(Trace { columns, rows }))
+}
+
+fn part1_trace(instructions: &Instructions, params: &Params) -> Result<Answer, AocError> {
+    trace(instructions, params, |dial, clicks| ((dial.position + clicks).rem_euclid(dial.positions) == 0) as u64)
+}
+
+fn part2_trace(instructions: &Instructions, params: &Params) -> Result<Answer, AocError> {
+    trace(instructions, params, Dial::zeros_passed)
+}
+
+/// `size` rotations of up to 999 clicks either way
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| {
+        let direction = if rng.chance(50) { 'L' } else { 'R' };
+        format!("{direction}{}\n", rng.between(1, 999))
+    }).collect()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u64 = 1;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "3", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "6", params: &[] }),
+    ];
+    const PARAMS: &'static [Param] = &[
+        Param { name: "start", kind: ParamKind::Int, default: "50", description: "where the dial starts" },
+        Param { name: "positions", kind: ParamKind::Int, default: "100", description: "how many positions the dial has" },
+        Param { name: "dials", kind: ParamKind::Text, default: "", description: "rings for named dials, like a=60@10 for 60 positions starting at 10" },
+    ];
+    const VARIANTS: &'static [Variant<Day1>] = &[
+        Variant { part: 1, name: "reference", solve: part1_reference },
+        Variant { part: 1, name: "trace", solve: part1_trace },
+        Variant { part: 2, name: "stepping", solve: part2_stepping },
+        Variant { part: 2, name: "trace", solve: part2_trace },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    /// Rotations, each turning the puzzle's one dial or a named one
+    type Parsed = Instructions;
+
+    fn parse(input: &str) -> Result<Instructions, AocError> {
+        dial::parse_instructions(input)
+    }
+
+    /// How many rotations leave a dial on 0
+    fn part1(instructions: &Instructions, params: &Params) -> Result<Answer, AocError> {
+        run_dials(instructions, params, |dial| dial.ends_on_zero)
+    }
+
+    /// How many times a dial passes 0, counted without stepping through each rotation
+    fn part2(instructions: &Instructions, params: &Params) -> Result<Answer, AocError> {
+        run_dials(instructions, params, |dial| dial.passes_zero)
+    }
+}
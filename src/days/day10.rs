@@ -1,9 +1,21 @@
 #![feature(portable_simd)]
 
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::parse;
+use crate::params::Params;
+use crate::days::{Example, Solution, Variant};
 use leptos::prelude::*;
 use std::simd::prelude::*;
 
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
 use std::collections::{HashMap, HashSet};
 use rustc_hash::FxHashSet;
 use rustc_hash::FxHashMap;
@@ -28,7 +40,7 @@ struct Joltage {
 }
 
 #[derive(Clone, Debug)]
-struct Machine {
+pub struct Machine {
     indicator_goal: Indicator,
     wiring: Vec<Mask>,
     joltage: Joltage,
@@ -41,28 +53,32 @@ fn toggle_indicator(indicator: Indicator, mask: Mask) -> Indicator {
     }
 }
 
-fn parse_indicator(str: &str) -> Result<Indicator, JsError> {
-    let mut indicator: Vec<i16> = str.chars().filter_map(|c| match c {
-        '[' | ']' => {
-            None
-        },
-        '.' => Some(Ok(0)),
-        '#' => Some(Ok(1)),
-        _ => Some(Err(JsError::new(&format!("Unexpected character {}", c)))),
-    }).collect::<Result<Vec<i16>, JsError>>()?;
+fn parse_indicator(input: &str, str: &str) -> Result<Indicator, AocError> {
+    let lights = parse::bracketed(input, str, '[', ']', "indicator lights")?;
+    let mut indicator: Vec<i16> = lights.char_indices().map(|(idx, c)| match c {
+        '.' => Ok(0),
+        '#' => Ok(1),
+        _ => Err(AocError::parse_at(input, &lights[idx..], format!("expected '.' or '#' in indicator, found {c:?}"))),
+    }).collect::<Result<Vec<i16>, AocError>>()?;
+    if indicator.len() > 16 {
+        return Err(AocError::parse_at(input, str, "indicators have at most 16 lights"));
+    }
     indicator.resize(16, 0);
 
     let num = i16x16::from_slice(indicator.as_slice());
     Ok(Indicator { val: num })
 }
 
-fn parse_wiring(str: &[&str]) -> Result<Vec<Mask>, JsError> {
+fn parse_wiring(input: &str, str: &[&str]) -> Result<Vec<Mask>, AocError> {
     let wiring: Vec<Mask> = str.iter().map(|piece| {
-        let nums = piece.split(",").map(|item| {
-            str::parse::<i16>(
-                item.replace("(", "").replace(")", "").as_str()
-            ).or_else(|_| Err(JsError::new("Couldn't parse wiring")))
-        }).collect::<Result<Vec<i16>, JsError>>()?;
+        let lights = parse::bracketed(input, piece, '(', ')', "wiring")?;
+        let nums = parse::list(lights, ',').map(|item| {
+            let num: i16 = parse::int(input, item, "a light number")?;
+            if !(0..16).contains(&num) {
+                return Err(AocError::parse_at(input, item, format!("wiring {num} is not between 0 and 15")));
+            }
+            Ok(num)
+        }).collect::<Result<Vec<i16>, AocError>>()?;
 
         let mut mask = Vec::new();
         mask.resize(16, 0);
@@ -72,17 +88,17 @@ fn parse_wiring(str: &[&str]) -> Result<Vec<Mask>, JsError> {
 
         let val = i16x16::from_slice(mask.as_slice());
         Ok(Mask { val })
-    }).collect::<Result<Vec<Mask>, JsError>>()?;
+    }).collect::<Result<Vec<Mask>, AocError>>()?;
 
     Ok(wiring)
 }
 
-fn parse_joltage(str: &str) -> Result<(Joltage, usize), JsError> {
-    let mut joltage: Vec<i16> = str.replace("{", "").replace("}", "").split(",").map(|piece| {
-        str::parse::<i16>(piece).or_else(
-            |err| Err(JsError::new(&format!("Couldn't parse joltage {:?} {:?}", err, piece)))
-        )
-    }).collect::<Result<Vec<i16>, JsError>>()?;
+fn parse_joltage(input: &str, str: &str) -> Result<(Joltage, usize), AocError> {
+    let joltages = parse::bracketed(input, str, '{', '}', "joltage requirements")?;
+    let mut joltage: Vec<i16> = parse::ints(input, joltages, ',', "a joltage")?;
+    if joltage.len() > 16 {
+        return Err(AocError::parse_at(input, str, "joltages have at most 16 entries"));
+    }
     let len = joltage.len();
     joltage.resize(16, 0);
     Ok((Joltage {
@@ -90,21 +106,24 @@ fn parse_joltage(str: &str) -> Result<(Joltage, usize), JsError> {
     }, len))
 }
 
-fn parse_input(input: &str) -> Result<Vec<Machine>, JsError> {
-    let machines = input.lines().map(|line| {
+fn parse_input(input: &str) -> Result<Vec<Machine>, AocError> {
+    let machines = parse::lines(input).map(|line| {
         let pieces: Vec<&str> = line.split_whitespace().collect();
-        let first = pieces.first().ok_or_else(|| JsError::new("couldn't find first" ))?;
-        let middle = pieces.get(1..pieces.len() - 1).ok_or_else(|| JsError::new("couldn't find middle" ))?;
-        let last = pieces.last().ok_or_else(|| JsError::new("couldn't find last" ))?;
+        if pieces.len() < 2 {
+            return Err(AocError::parse_at(input, line, "expected an indicator, wiring and joltage"));
+        }
+        let first = pieces.first().ok_or_else(|| AocError::parse_at(input, line, "couldn't find first" ))?;
+        let middle = pieces.get(1..pieces.len() - 1).ok_or_else(|| AocError::parse_at(input, line, "couldn't find middle" ))?;
+        let last = pieces.last().ok_or_else(|| AocError::parse_at(input, line, "couldn't find last" ))?;
 
-        let joltage_tup = parse_joltage(last)?;
+        let joltage_tup = parse_joltage(input, last)?;
         Ok(Machine {
-            indicator_goal: parse_indicator(first)?,
-            wiring: parse_wiring(middle)?,
+            indicator_goal: parse_indicator(input, first)?,
+            wiring: parse_wiring(input, middle)?,
             joltage: joltage_tup.0,
             joltage_len: joltage_tup.1,
         })
-    }).collect::<Result<Vec<Machine>, JsError>>()?;
+    }).collect::<Result<Vec<Machine>, AocError>>()?;
 
     Ok(machines)
 }
@@ -130,13 +149,139 @@ fn calc_min_presses_part_1(machine: &Machine) -> u32 {
     }
 }
 
-pub fn day10_part1(input: &str) -> Result<String, JsError> {
-    let machines = parse_input(input)?;
+/// Pressing a button twice undoes it, so the fewest presses is the smallest set
+/// of buttons whose toggles combine to the goal. Tries every set instead of searching
+fn calc_min_presses_part_1_subsets(machine: &Machine) -> Result<u32, AocError> {
+    let num_buttons = machine.wiring.len();
+    if num_buttons > 24 {
+        return Err(AocError::invalid(format!("{num_buttons} buttons is too many to try every combination")));
+    }
+
+    let mut min_presses = None;
+    for subset in 0u32..(1 << num_buttons) {
+        let presses = subset.count_ones();
+        if min_presses.is_some_and(|min| presses >= min) {
+            continue;
+        }
+
+        let mut indicator = Indicator { val: i16x16::splat(0) };
+        for (idx, mask) in machine.wiring.iter().enumerate() {
+            if subset & (1 << idx) != 0 {
+                indicator = toggle_indicator(indicator, *mask);
+            }
+        }
+        if indicator == machine.indicator_goal {
+            min_presses = Some(presses);
+        }
+    }
+
+    min_presses.ok_or_else(|| AocError::no_solution("no combination of buttons reaches the indicator goal"))
+}
+
+fn part1_subsets(machines: &[Machine], _params: &Params) -> Result<Answer, AocError> {
     let sum = machines.iter().map(|machine| {
-        calc_min_presses_part_1(machine)
-    }).sum::<u32>();
-    Ok(sum.to_string())
-    // Ok(format!("{:?}", machines))
+        calc_min_presses_part_1_subsets(machine)
+    }).sum::<Result<u32, AocError>>()?;
+    Ok(Answer::int(sum as u128))
+}
+
+/// How many search steps the reference gives a machine before giving up
+const REFERENCE_STEP_LIMIT: u64 = 100_000_000;
+
+/// Tries press counts light by light, always taking next the light with the fewest
+/// buttons left to settle it. That light's remaining joltage is shared out between
+/// those buttons every way it can be, and each button is then done with
+fn calc_min_presses_part_2_reference(machine: &Machine) -> Result<u64, AocError> {
+    let lights = machine.joltage_len;
+    let buttons: Vec<Vec<usize>> = machine.wiring.iter()
+        .map(|mask| (0..lights).filter(|&light| mask.val[light] != 0).collect())
+        .collect();
+    let mut needed: Vec<i64> = (0..lights).map(|light| machine.joltage.val[light] as i64).collect();
+
+    struct Search<'a> {
+        buttons: &'a [Vec<usize>],
+        best: Option<u64>,
+        steps: u64,
+    }
+
+    impl Search<'_> {
+        fn settle(&mut self, needed: &mut [i64], pressed: &mut [bool], presses: u64) -> Result<(), AocError> {
+            self.steps += 1;
+            if self.steps > REFERENCE_STEP_LIMIT {
+                return Err(AocError::invalid("machine is too big to search every press count"));
+            }
+            let most_needed = needed.iter().copied().max().unwrap_or(0) as u64;
+            if self.best.is_some_and(|best| presses + most_needed >= best) {
+                return Ok(());
+            }
+            if most_needed == 0 {
+                self.best = Some(presses);
+                return Ok(());
+            }
+
+            let free = |light: usize| -> Vec<usize> {
+                (0..self.buttons.len())
+                    .filter(|&button| !pressed[button] && self.buttons[button].contains(&light))
+                    .collect()
+            };
+            let Some(light) = (0..needed.len())
+                .filter(|&light| needed[light] > 0)
+                .min_by_key(|&light| (free(light).len(), -needed[light]))
+            else {
+                return Ok(());
+            };
+            let sharing = free(light);
+            if sharing.is_empty() {
+                return Ok(());
+            }
+
+            for &button in &sharing {
+                pressed[button] = true;
+            }
+            let result = self.share(&sharing, needed[light], needed, pressed, presses);
+            for &button in &sharing {
+                pressed[button] = false;
+            }
+            result
+        }
+
+        /// Presses the first of `sharing` each possible number of times up to
+        /// `remaining`, with the last taking whatever is left over
+        fn share(&mut self, sharing: &[usize], remaining: i64, needed: &mut [i64], pressed: &mut [bool], presses: u64) -> Result<(), AocError> {
+            let Some((&button, rest)) = sharing.split_first() else {
+                return self.settle(needed, pressed, presses);
+            };
+            let lights = &self.buttons[button];
+            let most = lights.iter().map(|&light| needed[light]).min().unwrap_or(0).min(remaining);
+            let counts = if rest.is_empty() { remaining..=remaining } else { 0..=most };
+            if *counts.end() > most {
+                return Ok(());
+            }
+
+            for count in counts.rev() {
+                for &light in lights {
+                    needed[light] -= count;
+                }
+                let result = self.share(rest, remaining - count, needed, pressed, presses + count as u64);
+                for &light in lights {
+                    needed[light] += count;
+                }
+                result?;
+            }
+            Ok(())
+        }
+    }
+
+    let mut search = Search { buttons: &buttons, best: None, steps: 0 };
+    search.settle(&mut needed, &mut vec![false; buttons.len()], 0)?;
+    search.best.ok_or_else(|| AocError::no_solution("no press counts reach the joltage goal"))
+}
+
+fn part2_reference(machines: &[Machine], _params: &Params) -> Result<Answer, AocError> {
+    let sum = machines.iter().map(|machine| {
+        calc_min_presses_part_2_reference(machine)
+    }).sum::<Result<u64, AocError>>()?;
+    Ok(Answer::int(sum as u128))
 }
 
 fn transpose(masks: &Vec<i64x16>) -> Vec<i64x16> {
@@ -153,7 +298,7 @@ fn transpose(masks: &Vec<i64x16>) -> Vec<i64x16> {
     transposed
 }
 
-fn reduce_rows(machine: &Machine) -> Result<Machine, JsError> {
+fn reduce_rows(machine: &Machine) -> Result<Machine, AocError> {
     let mut masks_transposed = transpose(&machine.wiring.iter().map(
         |mask| i64x16::from_array(mask.val.as_array().map(|x| x as i64))
     ).collect());
@@ -164,21 +309,7 @@ fn reduce_rows(machine: &Machine) -> Result<Machine, JsError> {
     let num_rows = machine.joltage_len;
     let mut divisors: Vec<u64> = (0..num_rows).map(|_| 1).collect();
 
-    let print_matrix_transposed = |_masks_transposed: &Vec<i64x16>, _joltage: &i64x16, _divisors: &Vec<u64>| {
-        for row in 0..num_rows {
-            for col in 0..num_cols {
-                print!("{:>5}", _masks_transposed[row][col] /*as f64 / _divisors[row] as f64*/);
-            }
-            println!(" = {:>5}", _joltage[row] /*as f64 /  _divisors[row] as f64*/);
-        }
-        println!();
-        println!();
-
-    };
-
     loop {
-        // print_matrix_transposed(&masks_transposed, &joltage, &divisors);
-
         let mut nonzero_col = None;
         for col in bound_col..num_cols {
             for row in bound_row..num_rows {
@@ -298,7 +429,6 @@ fn reduce_rows(machine: &Machine) -> Result<Machine, JsError> {
 
         }
 
-        print_matrix_transposed(&masks_transposed, &joltage, &divisors);
         // Now consider the part of the matrix below the top row and to the right of the column under consideration:
         // if there are no such rows or columns, stop since the procedure is finished.
         // Otherwise, carry out the same procedure on the new matrix.
@@ -360,11 +490,11 @@ fn calc_gcd(row: i64x16, other: i64) -> i64 {
     }
 }
 
-fn remove_empty_rows(machine: &Machine) -> Result<Machine, JsError> {
+fn remove_empty_rows(machine: &Machine) -> Result<Machine, AocError> {
     let len_masks = machine.wiring.len();
     let last_nonzero_rows = (0..len_masks).rev().find(
         |&idx| machine.wiring[idx].val != i16x16::splat(0)
-    ).ok_or_else(|| JsError::new("no nonzero row found"))?;
+    ).ok_or_else(|| AocError::invalid("no nonzero row found"))?;
     let to_len = last_nonzero_rows + 1;
 
     Ok(Machine {
@@ -410,7 +540,7 @@ fn calc_sorted_masks(machine: &Machine) -> Vec<Mask> {
 
 fn calc_constraints(old_constraints: &Option<Vec<Vec<Option<u16>>>>, new_constraints: &mut Vec<Vec<Option<u16>>>,
                     joltage: i16, old_joltages: &Vec<u16>,
-                    machine: &Machine, mask_val_and_mask_idx: &Vec<(i16, usize)>, possibility_path: &mut Vec<u16>) -> Result<(), JsError> {
+                    machine: &Machine, mask_val_and_mask_idx: &Vec<(i16, usize)>, possibility_path: &mut Vec<u16>) -> Result<(), AocError> {
     if mask_val_and_mask_idx.len() == 1 {
         let (val, mask_idx) = mask_val_and_mask_idx[0];
         // shortcut to handle one variable solutions
@@ -556,7 +686,7 @@ fn calc_constraints(old_constraints: &Option<Vec<Vec<Option<u16>>>>, new_constra
             for possibility in range {
                 possibility_path.push(possibility);
                 calc_constraints(old_constraints, new_constraints, joltage, old_joltages, machine, &mask_val_and_mask_idx, possibility_path)?;
-                possibility_path.pop().ok_or_else(|| JsError::new("pop failed"))?;
+                possibility_path.pop().ok_or_else(|| AocError::invalid("pop failed"))?;
             }
         }
     }
@@ -578,7 +708,7 @@ fn _satisfies_constraint(machine: &Machine, constraint_row: &Vec<Option<u16>>) -
     true
 }
 
-fn sort_masks(machine: &Machine) -> Result<Machine, JsError> {
+fn sort_masks(machine: &Machine) -> Result<Machine, AocError> {
     // sort rows by lowest count of coefficients first
 
     let mut wiring_counts: Vec<_> = (0..machine.joltage_len).map(|row_num| {
@@ -608,7 +738,7 @@ fn sort_masks(machine: &Machine) -> Result<Machine, JsError> {
     })
 }
 
-fn calc_min_presses_part_2(old_machine: &Machine) -> Result<u16, JsError> {
+fn calc_min_presses_part_2(old_machine: &Machine) -> Result<u16, AocError> {
     let old_joltages: Vec<u16> = old_machine.wiring.iter().map(|mask| {
         let mut max_joltage = 0;
         for row in 0..old_machine.joltage_len {
@@ -650,27 +780,100 @@ fn calc_min_presses_part_2(old_machine: &Machine) -> Result<u16, JsError> {
         }
     }
 
-    Ok(
-        constraints.ok_or_else(
-            || JsError::new("no constraints calculated")
-        )?.iter().map(
-            |items| {
-                items.iter().map(|item| item.ok_or_else(
-                    || JsError::new("None found in constraints")
-                ).unwrap()).sum()
-            }
-        ).min().ok_or_else(
-            || JsError::new("No minimum found")
-        )?
+    let totals = constraints.ok_or_else(
+        || AocError::no_solution("no constraints calculated")
+    )?.iter().map(
+        |items| {
+            items.iter().map(|item| item.ok_or_else(
+                || AocError::invalid("None found in constraints")
+            )).sum::<Result<u16, AocError>>()
+        }
+    ).collect::<Result<Vec<u16>, AocError>>()?;
+    totals.into_iter().min().ok_or_else(
+        || AocError::no_solution("No minimum found")
     )
 }
 
+/// `size` machines with 3 to 10 lights and 2 to 12 different buttons. The lights are some
+/// set of buttons pressed once and the joltages some number of presses of each,
+/// so both parts always have an answer
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| {
+        let num_lights = rng.between(3, 10) as usize;
+        // like the puzzle's, a machine has at most a couple more buttons than lights and no
+        // two wired the same, part 2 slows to a crawl with many more free presses
+        let num_buttons = rng.between(2, num_lights as u64 + 2) as usize;
+        let mut buttons: Vec<Vec<usize>> = Vec::with_capacity(num_buttons);
+        while buttons.len() < num_buttons {
+            let mut wired: Vec<usize> = (0..num_lights).filter(|_| rng.chance(40)).collect();
+            if wired.is_empty() {
+                wired.push(rng.below(num_lights as u64) as usize);
+            }
+            if !buttons.contains(&wired) {
+                buttons.push(wired);
+            }
+        }
+
+        let mut lights = vec![false; num_lights];
+        let mut joltages = vec![0; num_lights];
+        for button in &buttons {
+            let toggle = rng.chance(50);
+            let presses = rng.between(0, 15);
+            for &light in button {
+                lights[light] ^= toggle;
+                joltages[light] += presses;
+            }
+        }
+        // part 1 never finishes if the goal is all lights off, so press the first button instead
+        if !lights.contains(&true) {
+            for &light in &buttons[0] {
+                lights[light] = true;
+            }
+        }
+
+        let lights: String = lights.iter().map(|on| if *on { '#' } else { '.' }).collect();
+        let buttons: Vec<String> = buttons.iter().map(|button| {
+            let wired: Vec<String> = button.iter().map(usize::to_string).collect();
+            format!("({})", wired.join(","))
+        }).collect();
+        let joltages: Vec<String> = joltages.iter().map(u64::to_string).collect();
+        format!("[{lights}] {} {{{}}}\n", buttons.join(" "), joltages.join(","))
+    }).collect()
+}
+
+pub struct Day10;
 
-pub fn day10_part2(input: &str) -> Result<String, JsError> {
-    let machines = parse_input(input)?;
-    let vals: Vec<_> = machines.iter().map(|machine| {
-        calc_min_presses_part_2(machine)
-    }).collect::<Result<_, _>>()?;
-    let sum = vals.iter().sum::<u16>();
-    Ok(sum.to_string())
+impl Solution for Day10 {
+    const DAY: u64 = 10;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "7", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "33", params: &[] }),
+    ];
+    const VARIANTS: &'static [Variant<Day10>] = &[
+        Variant { part: 1, name: "subsets", solve: |machines, params| part1_subsets(machines, params) },
+        Variant { part: 2, name: "reference", solve: |machines, params| part2_reference(machines, params) },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Vec<Machine>, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(machines: &Vec<Machine>, _params: &Params) -> Result<Answer, AocError> {
+        let sum = machines.iter().map(|machine| {
+            calc_min_presses_part_1(machine)
+        }).sum::<u32>();
+        Ok(Answer::int(sum as u128))
+    }
+
+    fn part2(machines: &Vec<Machine>, _params: &Params) -> Result<Answer, AocError> {
+        let vals: Vec<_> = machines.iter().map(|machine| {
+            calc_min_presses_part_2(machine)
+        }).collect::<Result<_, _>>()?;
+        let sum = vals.iter().sum::<u16>();
+        Ok(Answer::int(sum as u128))
+    }
 }
@@ -1,16 +1,51 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::params::{Param, ParamKind, Params};
+use crate::days::{Example, Solution, Variant};
 use leptos::prelude::*;
 use leptos::web_sys::console::log_1;
 
-struct Graph {
+/// Worked example from the part 1 puzzle text
+pub const EXAMPLE_PART1: &str = "\
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out
+";
+
+/// Worked example from the part 2 puzzle text, part 1's has no "svr"
+pub const EXAMPLE_PART2: &str = "\
+svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out
+";
+
+pub struct Graph {
     node_ids: HashMap<String, u64>,
     links: HashMap<u64, Vec<u64>>,
     back_links: HashMap<u64, Vec<u64>>,
 }
 
-fn parse_input(input: &str) -> Result<Graph, JsError> {
+fn parse_input(input: &str) -> Result<Graph, AocError> {
     let mut links: HashMap<u64, Vec<u64>> = HashMap::new();
     let mut node_ids: HashMap<String, u64> = HashMap::new();
 
@@ -22,10 +57,10 @@ fn parse_input(input: &str) -> Result<Graph, JsError> {
 
     for line in input.lines() {
         let mut split = line.split(':');
-        let key = split.next().ok_or_else(|| JsError::new("couldn't find first" ))?;
+        let key = split.next().ok_or_else(|| AocError::parse_at(input, line, "couldn't find first" ))?;
         let key_node_id = get_node_id(key);
 
-        let rest = split.next().ok_or_else(|| JsError::new("couldn't find rest" ))?;
+        let rest = split.next().ok_or_else(|| AocError::parse_at(input, line, "expected ':' after the device name" ))?;
         let mut whitespace_split = rest.split_whitespace();
 
         let mut children = Vec::new();
@@ -46,14 +81,20 @@ fn parse_input(input: &str) -> Result<Graph, JsError> {
     Ok(Graph { node_ids, links, back_links })
 }
 
-fn _count_paths_part1(graph: &Graph, current_node: u64, paths_count: &mut HashMap<u64, usize>) -> Result<usize, JsError> {
+fn node_name(graph: &Graph, node_id: u64) -> String {
+    graph.node_ids.iter().find(|(_, id)| **id == node_id).map(|(name, _)| name.clone()).unwrap_or_else(
+        || node_id.to_string()
+    )
+}
+
+fn _count_paths_part1(graph: &Graph, current_node: u64, paths_count: &mut HashMap<u64, usize>) -> Result<usize, AocError> {
     let entry = paths_count.entry(current_node);
     if let Entry::Occupied(entry) = entry {
         return Ok(*entry.get());
     }
 
     let children = graph.links.get(&current_node).ok_or_else(
-        || JsError::new("couldn't find children" )
+        || AocError::missing_node(format!("{} has no outputs listed", node_name(graph, current_node)))
     )?;
     let mut count = 0;
     for child in children {
@@ -65,23 +106,6 @@ fn _count_paths_part1(graph: &Graph, current_node: u64, paths_count: &mut HashMa
     Ok(count)
 }
 
-pub fn day11_part1(input: &str) -> Result<String, JsError> {
-    let graph = parse_input(input)?;
-
-    let end_node = graph.node_ids.get("out").ok_or_else(|| JsError::new("couldn't find end node" ))?;
-    let start_node = graph.node_ids.get("you").ok_or_else(|| JsError::new("couldn't find start node" ))?;
-
-    let mut visited_count = HashMap::new();
-    visited_count.insert(*end_node, 1);
-    _count_paths_part1(&graph, *start_node, &mut visited_count)?;
-
-    let num_paths = visited_count.get(start_node).ok_or_else(
-        || JsError::new("couldn't find path" )
-    )?;
-    Ok(num_paths.to_string())
-}
-
-
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct Part2Count {
     none: u128,
@@ -90,8 +114,10 @@ struct Part2Count {
     all: u128
 }
 
-fn _count_paths_part2(graph: &Graph, current_node: u64, paths_count: &mut HashMap<u64, Part2Count>) -> Result<Part2Count, JsError> {
-    //let current_node_name = graph.node_ids.iter().find(|(_, id)| **id == current_node).ok_or_else(|| JsError::new("couldn't find node name" ))?.0;
+/// Counts paths from `current_node` to the end, split by which of the two
+/// required devices (`dac` and `fft` in the puzzle) they pass through
+fn _count_paths_part2(graph: &Graph, current_node: u64, dac: &str, fft: &str, paths_count: &mut HashMap<u64, Part2Count>) -> Result<Part2Count, AocError> {
+    //let current_node_name = graph.node_ids.iter().find(|(_, id)| **id == current_node).ok_or_else(|| AocError::invalid("couldn't find node name"))?.0;
     //log_1(&format!("calling with {:?}", current_node_name).into());
     let entry = paths_count.entry(current_node);
     if let Entry::Occupied(entry) = entry {
@@ -100,17 +126,17 @@ fn _count_paths_part2(graph: &Graph, current_node: u64, paths_count: &mut HashMa
     }
 
     let children = graph.links.get(&current_node).ok_or_else(
-        || JsError::new("couldn't find children" )
+        || AocError::missing_node(format!("{} has no outputs listed", node_name(graph, current_node)))
     )?;
 
-    let visited_dac = graph.node_ids.get("dac").ok_or_else(|| JsError::new("couldn't find dac" ))? == &current_node;
+    let visited_dac = graph.node_ids.get(dac).ok_or_else(|| AocError::missing_node(dac))? == &current_node;
     let visited_fft =
-            graph.node_ids.get("fft").ok_or_else(|| JsError::new("couldn't find fft" ))? == &current_node
+            graph.node_ids.get(fft).ok_or_else(|| AocError::missing_node(fft))? == &current_node
     ;
 
     let mut count = Part2Count { none: 0, dac_only: 0, fft_only: 0, all: 0 };
     for child in children {
-        let child_count = _count_paths_part2(graph, *child, paths_count)?;
+        let child_count = _count_paths_part2(graph, *child, dac, fft, paths_count)?;
         count = Part2Count {
             none: count.none + child_count.none,
             all: count.all + child_count.all,
@@ -165,18 +191,180 @@ fn _count_paths_part2(graph: &Graph, current_node: u64, paths_count: &mut HashMa
     Ok(count)
 }
 
-pub fn day11_part2(input: &str) -> Result<String, JsError> {
-    let graph = parse_input(input)?;
+/// Counts paths from `start` to `end` by walking devices in topological order
+/// (Kahn's algorithm) instead of recursing. The counts are split by which of
+/// `stops` a path has passed through, indexed by a bitmask of them
+fn count_paths_reference(graph: &Graph, start: &str, end: &str, stops: &[&str]) -> Result<Vec<u128>, AocError> {
+    let node = |name: &str| graph.node_ids.get(name).copied().ok_or_else(|| AocError::missing_node(name));
+    let (start, end) = (node(start)?, node(end)?);
+    let stops: Vec<u64> = stops.iter().map(|name| node(name)).collect::<Result<_, _>>()?;
+    let stop_bits = |node: u64| stops.iter().enumerate()
+        .filter(|(_, stop)| **stop == node)
+        .fold(0, |bits, (idx, _)| bits | (1 << idx));
+    let outputs = |node: u64| -> Result<&[u64], AocError> {
+        if node == end {
+            return Ok(&[]);
+        }
+        graph.links.get(&node).map(Vec::as_slice).ok_or_else(
+            || AocError::missing_node(format!("{} has no outputs listed", node_name(graph, node)))
+        )
+    };
+
+    // how many wires into each device reachable from the start
+    let mut inputs: HashMap<u64, usize> = HashMap::from([(start, 0)]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for output in outputs(node)? {
+            let count = inputs.entry(*output).or_insert(0);
+            if *count == 0 && *output != start {
+                stack.push(*output);
+            }
+            *count += 1;
+        }
+    }
+
+    let mut counts: HashMap<u64, Vec<u128>> = HashMap::new();
+    counts.entry(start).or_insert_with(|| vec![0; 1 << stops.len()])[stop_bits(start)] = 1;
+    let mut ready = vec![start];
+    let mut visited = 0;
+    while let Some(node) = ready.pop() {
+        visited += 1;
+        let node_counts = counts.get(&node).cloned().unwrap_or_default();
+        for output in outputs(node)? {
+            let bits = stop_bits(*output);
+            let output_counts = counts.entry(*output).or_insert_with(|| vec![0; 1 << stops.len()]);
+            for (seen, count) in node_counts.iter().enumerate() {
+                output_counts[seen | bits] += count;
+            }
+            let remaining = inputs.get_mut(output).expect("every output was counted");
+            *remaining -= 1;
+            if *remaining == 0 {
+                ready.push(*output);
+            }
+        }
+    }
+    if visited < inputs.len() || inputs[&start] != 0 {
+        return Err(AocError::invalid("devices are wired in a loop"));
+    }
 
-    let end_node = graph.node_ids.get("out").ok_or_else(|| JsError::new("couldn't find end node" ))?;
-    let start_node = graph.node_ids.get("svr").ok_or_else(|| JsError::new("couldn't find start node" ))?;
+    Ok(counts.remove(&end).unwrap_or_else(|| vec![0; 1 << stops.len()]))
+}
 
-    let mut visited_count = HashMap::new();
-    visited_count.insert(*end_node, Part2Count { none: 1, all: 0, fft_only: 0, dac_only: 0 } );
-    _count_paths_part2(&graph, *start_node, &mut visited_count)?;
+fn part1_reference(graph: &Graph, params: &Params) -> Result<Answer, AocError> {
+    let counts = count_paths_reference(graph, params.text("part1_start")?, params.text("end")?, &[])?;
+    Ok(Answer::int(counts[0]))
+}
 
-    let num_paths = visited_count.get(start_node).ok_or_else(
-        || JsError::new("couldn't find path" )
-    )?;
-    Ok(format!("{:?}", num_paths))
+fn part2_reference(graph: &Graph, params: &Params) -> Result<Answer, AocError> {
+    let stops = [params.text("first_stop")?, params.text("second_stop")?];
+    let counts = count_paths_reference(graph, params.text("part2_start")?, params.text("end")?, &stops)?;
+    Ok(Answer::int(counts[0b11]))
+}
+
+/// The devices every generated input has, in the order they're wired
+const GENERATED_DEVICES: [&str; 5] = ["svr", "you", "dac", "fft", "out"];
+
+/// Keeps generated path counts well inside part 1's usize
+const GENERATED_PATH_LIMIT: u128 = 1_000_000_000_000;
+
+/// `size` devices that only output to devices after them, so there are no loops,
+/// with "svr" first, "out" last and "you", "dac" and "fft" in between. An output
+/// is left off if it would give a device more than 10^12 paths to "out"
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(GENERATED_DEVICES.len());
+    let mut names: Vec<String> = GENERATED_DEVICES[1..4].iter().map(|name| name.to_string()).collect();
+    while names.len() < size - 2 {
+        let name: String = (0..3).map(|_| char::from(b'a' + rng.below(26) as u8)).collect();
+        if !names.contains(&name) && !GENERATED_DEVICES.contains(&name.as_str()) {
+            names.push(name);
+        }
+    }
+    rng.shuffle(&mut names);
+    names.insert(0, "svr".to_string());
+    names.push("out".to_string());
+
+    // paths from each device to "out", filled in from the end
+    let mut paths = vec![0u128; size];
+    paths[size - 1] = 1;
+    let mut lines = Vec::with_capacity(size - 1);
+    for idx in (0..size - 1).rev() {
+        let last = (idx + 6).min(size - 1);
+        let mut outputs: Vec<usize> = Vec::new();
+        for _ in 0..rng.between(1, 3) {
+            let output = rng.between(idx as u64 + 1, last as u64) as usize;
+            if outputs.is_empty() || (!outputs.contains(&output) && paths[idx] + paths[output] <= GENERATED_PATH_LIMIT) {
+                paths[idx] += paths[output];
+                outputs.push(output);
+            }
+        }
+        let outputs: Vec<&str> = outputs.iter().map(|output| names[*output].as_str()).collect();
+        lines.push(format!("{}: {}\n", names[idx], outputs.join(" ")));
+    }
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u64 = 11;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE_PART1, answer: "5", params: &[] }),
+        Some(Example { input: EXAMPLE_PART2, answer: "2", params: &[] }),
+    ];
+    const PARAMS: &'static [Param] = &[
+        Param { name: "part1_start", kind: ParamKind::Text, default: "you", description: "device part 1 counts paths from" },
+        Param { name: "part2_start", kind: ParamKind::Text, default: "svr", description: "device part 2 counts paths from" },
+        Param { name: "end", kind: ParamKind::Text, default: "out", description: "device every path ends at" },
+        Param { name: "first_stop", kind: ParamKind::Text, default: "dac", description: "device part 2's paths must visit" },
+        Param { name: "second_stop", kind: ParamKind::Text, default: "fft", description: "other device part 2's paths must visit" },
+    ];
+    const VARIANTS: &'static [Variant<Day11>] = &[
+        Variant { part: 1, name: "reference", solve: part1_reference },
+        Variant { part: 2, name: "reference", solve: part2_reference },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Graph;
+
+    fn parse(input: &str) -> Result<Graph, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(graph: &Graph, params: &Params) -> Result<Answer, AocError> {
+        let (end, start) = (params.text("end")?, params.text("part1_start")?);
+        let end_node = graph.node_ids.get(end).ok_or_else(|| AocError::missing_node(end))?;
+        let start_node = graph.node_ids.get(start).ok_or_else(|| AocError::missing_node(start))?;
+
+        let mut visited_count = HashMap::new();
+        visited_count.insert(*end_node, 1);
+        _count_paths_part1(graph, *start_node, &mut visited_count)?;
+
+        let num_paths = visited_count.get(start_node).ok_or_else(
+            || AocError::no_solution("couldn't find path")
+        )?;
+        Ok(Answer::int(*num_paths as u128))
+    }
+
+    fn part2(graph: &Graph, params: &Params) -> Result<Answer, AocError> {
+        let (end, start) = (params.text("end")?, params.text("part2_start")?);
+        let (dac, fft) = (params.text("first_stop")?, params.text("second_stop")?);
+        let end_node = graph.node_ids.get(end).ok_or_else(|| AocError::missing_node(end))?;
+        let start_node = graph.node_ids.get(start).ok_or_else(|| AocError::missing_node(start))?;
+
+        let mut visited_count = HashMap::new();
+        visited_count.insert(*end_node, Part2Count { none: 1, all: 0, fft_only: 0, dac_only: 0 } );
+        _count_paths_part2(graph, *start_node, dac, fft, &mut visited_count)?;
+
+        let num_paths = visited_count.get(start_node).ok_or_else(
+            || AocError::no_solution("couldn't find path")
+        )?;
+        Ok(
+            Answer::int(num_paths.all)
+                .with_diagnostic("none", num_paths.none)
+                .with_diagnostic(format!("{dac}_only"), num_paths.dac_only)
+                .with_diagnostic(format!("{fft}_only"), num_paths.fft_only)
+        )
+    }
 }
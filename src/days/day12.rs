@@ -1,10 +1,26 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::params::Params;
+use crate::days::{Example, Solution};
 use leptos::prelude::*;
 
-pub fn day12_part1(input: &str) -> Result<String, JsError> {
-    Ok("".to_string())
-}
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u64 = 12;
+    const EXAMPLES: [Option<Example>; 2] = [None, None];
+
+    type Parsed = ();
+
+    fn parse(_input: &str) -> Result<(), AocError> {
+        Ok(())
+    }
+
+    fn part1(_parsed: &(), _params: &Params) -> Result<Answer, AocError> {
+        Err(AocError::no_solution("day 12 part 1 is not solved yet"))
+    }
 
-pub fn day12_part2(input: &str) -> Result<String, JsError> {
-    Ok("".to_string())
+    fn part2(_parsed: &(), _params: &Params) -> Result<Answer, AocError> {
+        Err(AocError::no_solution("day 12 part 2 is not solved yet"))
+    }
 }
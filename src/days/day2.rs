@@ -1,17 +1,23 @@
-use leptos::wasm_bindgen::JsError;
-
-fn parse_ranges(input: &str) -> Result<Vec<(u128, u128)>, JsError> {
-    let pieces = input.split(",");
-    pieces.map(|piece| {
-        let trimmed_piece = piece.trim();
-        let mut numstrs = trimmed_piece.split("-");
-        let first: &str = numstrs.next().ok_or_else(|| JsError::new("No first number"))?;
-        let second: &str = numstrs.next().ok_or_else(|| JsError::new("No second number"))?;
-        let pair: (u128, u128) = (
-            str::parse::<u128>(first)?,
-            str::parse::<u128>(second)?,
-        );
-        Ok(pair)
+use std::ops::RangeInclusive;
+use crate::answer::{Answer, Trace};
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::parse;
+use crate::params::Params;
+use crate::days::{Example, Solution, Variant};
+
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124
+";
+
+fn parse_ranges(input: &str) -> Result<Vec<(u128, u128)>, AocError> {
+    parse::list(input.trim(), ',').map(|piece| {
+        let (start, end) = parse::range(input, piece, "an id")?;
+        if start > end {
+            return Err(AocError::parse_at(input, piece, format!("range {piece} starts after it ends")));
+        }
+        Ok((start, end))
     }).collect()
 }
 
@@ -25,42 +31,24 @@ fn calc_num_digits(num: u128) -> u32 {
     num_digits
 }
 
-fn is_invalid_id_part1(num: u128) -> Result<bool, JsError> {
+fn is_invalid_id_part1(num: u128) -> Result<bool, AocError> {
     // check if num is made of two repeating segments
 
     let num_digits = calc_num_digits(num);
     if num_digits % 2 != 0 {
         Ok(false)
     } else {
-        let tens: u128 = 10_u128.checked_pow(num_digits / 2).ok_or_else(|| JsError::new("Overflow"))?;
+        let tens: u128 = 10_u128.checked_pow(num_digits / 2).ok_or_else(|| AocError::overflow("power of ten"))?;
         let slice = num % tens;
         let copy = num / tens;
         Ok(slice == copy)
     }
 }
 
-pub fn day2_part1(input: &str) -> Result<String, JsError> {
-    let ranges = parse_ranges(input)?;
-    let mut invalid_ids: u128 = 0;
-
-    for range in ranges {
-        for num in range.0..=range.1 {
-            if is_invalid_id_part1(num)? {
-                invalid_ids = invalid_ids.checked_add(num).ok_or_else(
-                    || JsError::new("Overflow")
-                )?;
-            }
-        }    
-    }
-    
-    Ok(invalid_ids.to_string())
-    
-}
-
-fn check_invalid_part2(num: u128, num_digits: u32, num_digit_per_group: u32) -> Result<bool, JsError> {
+fn check_invalid_part2(num: u128, num_digits: u32, num_digit_per_group: u32) -> Result<bool, AocError> {
     let mut copy = num;
     let times = num_digits / num_digit_per_group;
-    let tens: u128 = 10_u128.checked_pow(num_digit_per_group).ok_or_else(|| JsError::new("Overflow"))?;
+    let tens: u128 = 10_u128.checked_pow(num_digit_per_group).ok_or_else(|| AocError::overflow("power of ten"))?;
     let slice = num % tens;
 
     for i in 0..times {
@@ -75,7 +63,7 @@ fn check_invalid_part2(num: u128, num_digits: u32, num_digit_per_group: u32) ->
     Ok(true)
 }
 
-fn is_invalid_id_part2(num: u128) -> Result<bool, JsError> {
+fn is_invalid_id_part2(num: u128) -> Result<bool, AocError> {
     // check if num is made of two repeating segments
 
     let num_digits = calc_num_digits(num);
@@ -91,20 +79,325 @@ fn is_invalid_id_part2(num: u128) -> Result<bool, JsError> {
     Ok(false)
 }
 
-pub fn day2_part2(input: &str) -> Result<String, JsError> {
-    let ranges = parse_ranges(input)?;
+/// What a `block_len` digit block is multiplied by to write it out `repeats` times,
+/// e.g. 1001 for a 3 digit block twice
+fn repeater(block_len: u32, repeats: u32) -> Option<u128> {
+    let shift = 10u128.checked_pow(block_len)?;
+    (1..repeats).try_fold(1u128, |repeater, _| repeater.checked_mul(shift)?.checked_add(1))
+}
+
+/// The ids in `start..=end` that are `len` digits long and repeat a `block_len`
+/// digit block are each their block times the `repeater`, so the blocks that land
+/// in range are consecutive. Returns the repeater and those blocks
+fn repeating_blocks(start: u128, end: u128, len: u32, block_len: u32) -> Option<(u128, RangeInclusive<u128>)> {
+    let repeater = repeater(block_len, len / block_len)?;
+    let smallest_block = 10u128.pow(block_len - 1);
+    let largest_block = 10u128.checked_pow(block_len).map_or(u128::MAX, |power| power - 1);
+    let low = start.div_ceil(repeater).max(smallest_block);
+    let high = (end / repeater).min(largest_block);
+    (low <= high).then_some((repeater, low..=high))
+}
+
+/// The sum of the ids in `start..=end` that are `len` digits long and repeat a
+/// `block_len` digit block, as an arithmetic series of their blocks
+fn sum_repeating(start: u128, end: u128, len: u32, block_len: u32) -> Result<u128, AocError> {
+    let overflow = || AocError::overflow("sum of invalid ids");
+    let Some((repeater, blocks)) = repeating_blocks(start, end, len, block_len) else {
+        return Ok(0);
+    };
+
+    let (low, high) = blocks.into_inner();
+    let (count, ends) = (high - low + 1, low + high);
+    let blocks = if count % 2 == 0 { (count / 2).checked_mul(ends) } else { count.checked_mul(ends / 2) };
+    blocks.and_then(|blocks| blocks.checked_mul(repeater)).ok_or_else(overflow)
+}
+
+/// How many ids in `start..=end` are `len` digits long and repeat a `block_len`
+/// digit block
+fn count_repeating(start: u128, end: u128, len: u32, block_len: u32) -> u128 {
+    repeating_blocks(start, end, len, block_len).map_or(0, |(_, blocks)| blocks.end() - blocks.start() + 1)
+}
+
+/// The digit lengths of the ids in `start..=end`
+fn lengths(start: u128, end: u128) -> RangeInclusive<u32> {
+    calc_num_digits(start).max(1)..=calc_num_digits(end)
+}
+
+fn sum_invalid_part1(start: u128, end: u128) -> Result<u128, AocError> {
+    lengths(start, end).filter(|len| len % 2 == 0).try_fold(0u128, |sum, len| {
+        sum.checked_add(sum_repeating(start, end, len, len / 2)?).ok_or_else(|| AocError::overflow("sum of invalid ids"))
+    })
+}
+
+fn count_invalid_part1(start: u128, end: u128) -> u128 {
+    lengths(start, end).filter(|len| len % 2 == 0).map(|len| count_repeating(start, end, len, len / 2)).sum()
+}
+
+/// Adds up `measure` of the ids repeating each block length, counting each id once.
+/// An id with a 2 digit block also repeats a 1 digit block if that's what its
+/// block is, so measuring every block length would count it twice. Going from the
+/// shortest, each block length keeps only the ids no shorter block explains, by
+/// taking off what the shorter lengths dividing it already counted
+fn by_shortest_block(
+    start: u128,
+    end: u128,
+    measure: impl Fn(u32, u32) -> Result<u128, AocError>,
+) -> Result<u128, AocError> {
+    let overflow = || AocError::overflow("sum of invalid ids");
+    let mut total: u128 = 0;
+    for len in lengths(start, end) {
+        // (block length, measure of the ids it's the shortest block of)
+        let mut shortest: Vec<(u32, u128)> = Vec::new();
+        for block_len in (1..len).filter(|block_len| len % block_len == 0) {
+            let repeating = measure(len, block_len)?;
+            let counted: u128 = shortest.iter().filter(|(shorter, _)| block_len % shorter == 0).map(|(_, ids)| ids).sum();
+            shortest.push((block_len, repeating - counted));
+        }
+        for (_, ids) in shortest {
+            total = total.checked_add(ids).ok_or_else(overflow)?;
+        }
+    }
+    Ok(total)
+}
+
+fn sum_invalid_part2(start: u128, end: u128) -> Result<u128, AocError> {
+    by_shortest_block(start, end, |len, block_len| sum_repeating(start, end, len, block_len))
+}
+
+fn count_invalid_part2(start: u128, end: u128) -> Result<u128, AocError> {
+    by_shortest_block(start, end, |len, block_len| Ok(count_repeating(start, end, len, block_len)))
+}
+
+/// Most ids an explanation lists. Past this they're still counted and summed
+const EXPLAIN_LIMIT: usize = 10_000;
+
+/// The ids in `start..=end` that repeat a block, smallest first, each with the
+/// block and how many times it's written. Part 1's ids (`only_twice`) are always
+/// two halves, part 2's are explained by their shortest block. Ids are worked out
+/// as they're asked for, so a wide range only costs as many as are taken
+fn repeating_ids(start: u128, end: u128, only_twice: bool) -> impl Iterator<Item = (u128, u128, u32)> {
+    lengths(start, end).flat_map(move |len| {
+        let block_lens: Vec<u32> = match only_twice {
+            true => (len % 2 == 0).then_some(len / 2).into_iter().collect(),
+            false => (1..len).filter(|block_len| len % block_len == 0).collect(),
+        };
+        // (repeater, repeats, blocks not yet taken) for each block length, shortest first
+        let mut sequences: Vec<(u128, u32, RangeInclusive<u128>)> = block_lens.into_iter().filter_map(|block_len| {
+            let (repeater, blocks) = repeating_blocks(start, end, len, block_len)?;
+            Some((repeater, len / block_len, blocks))
+        }).collect();
+        // the smallest next id of any block length, explained by the shortest block
+        // that makes it, with every sequence that makes it moved past it
+        std::iter::from_fn(move || {
+            let (id, block, repeats) = sequences.iter().filter(|(_, _, blocks)| !blocks.is_empty()).map(
+                |(repeater, repeats, blocks)| (blocks.start() * repeater, *blocks.start(), *repeats)
+            ).min_by_key(|(id, _, _)| *id)?;
+            for (repeater, _, blocks) in sequences.iter_mut() {
+                if !blocks.is_empty() && blocks.start() * *repeater == id {
+                    blocks.next();
+                }
+            }
+            Some((id, block, repeats))
+        })
+    })
+}
+
+/// Lists the invalid ids of each range with the block behind each one, like
+/// "446446 = 446 x2". The count and sum come from the same arithmetic as the
+/// default, so only the listed ids are ever written out
+fn explain(ranges: &[(u128, u128)], only_twice: bool) -> Result<Answer, AocError> {
+    let mut sum: u128 = 0;
+    let mut found: u128 = 0;
+    let mut rows = Vec::new();
+    // rows there would be without the limit
+    let mut wanted: u128 = 0;
+    for &(start, end) in ranges {
+        let range = format!("{start}-{end}");
+        let (ids, count) = match only_twice {
+            true => (sum_invalid_part1(start, end)?, count_invalid_part1(start, end)),
+            false => (sum_invalid_part2(start, end)?, count_invalid_part2(start, end)?),
+        };
+        sum = sum.checked_add(ids).ok_or_else(|| AocError::overflow("sum of invalid ids"))?;
+        found += count;
+        wanted += count.max(1);
+
+        if count == 0 && rows.len() < EXPLAIN_LIMIT {
+            rows.push(vec![range.clone(), "-".to_string(), "no invalid ids".to_string()]);
+        }
+        let room = EXPLAIN_LIMIT - rows.len();
+        for (id, block, repeats) in repeating_ids(start, end, only_twice).take(room) {
+            rows.push(vec![range.clone(), id.to_string(), format!("{id} = {block} x{repeats}")]);
+        }
+    }
+
+    let mut answer = Answer::int(sum).with_diagnostic("invalid ids", found);
+    if wanted > rows.len() as u128 {
+        answer = answer.with_diagnostic("listed", format!("the first {EXPLAIN_LIMIT} rows"));
+    }
+    let columns = ["range", "id", "explanation"].map(String::from).to_vec();
+    Ok(answer.with_trace(Trace { columns, rows }))
+}
+
+fn part1_explain(ranges: &[(u128, u128)], _params: &Params) -> Result<Answer, AocError> {
+    explain(ranges, true)
+}
+
+fn part2_explain(ranges: &[(u128, u128)], _params: &Params) -> Result<Answer, AocError> {
+    explain(ranges, false)
+}
+
+/// Checks every id in every range one at a time
+fn part1_every_id(ranges: &Vec<(u128, u128)>, _params: &Params) -> Result<Answer, AocError> {
+    let mut invalid_ids: u128 = 0;
+
+    for range in ranges {
+        for num in range.0..=range.1 {
+            if is_invalid_id_part1(num)? {
+                invalid_ids = invalid_ids.checked_add(num).ok_or_else(
+                    || AocError::overflow("sum of invalid ids")
+                )?;
+            }
+        }
+    }
+
+    Ok(Answer::int(invalid_ids))
+}
+
+/// Checks every id in every range one at a time
+fn part2_every_id(ranges: &Vec<(u128, u128)>, _params: &Params) -> Result<Answer, AocError> {
     let mut invalid_ids: u128 = 0;
 
     for range in ranges {
         for num in range.0..=range.1 {
             if is_invalid_id_part2(num)? {
                 invalid_ids = invalid_ids.checked_add(num).ok_or_else(
-                    || JsError::new("Overflow")
+                    || AocError::overflow("sum of invalid ids")
                 )?;
             }
         }
     }
 
-    Ok(invalid_ids.to_string())
+    Ok(Answer::int(invalid_ids))
+}
 
-}
\ No newline at end of file
+/// Whether the id's digits are one block written out at least twice, or exactly
+/// twice if `only_twice`, checked on the id as a string
+fn is_repeated_reference(num: u128, only_twice: bool) -> bool {
+    let digits = num.to_string();
+    (1..digits.len()).filter(|len| digits.len().is_multiple_of(*len) && (!only_twice || digits.len() == 2 * len)).any(
+        |len| digits[..len].repeat(digits.len() / len) == digits
+    )
+}
+
+fn sum_repeated_reference(ranges: &[(u128, u128)], only_twice: bool) -> Result<Answer, AocError> {
+    let mut sum: u128 = 0;
+    for &(start, end) in ranges {
+        for num in (start..=end).filter(|num| is_repeated_reference(*num, only_twice)) {
+            sum = sum.checked_add(num).ok_or_else(|| AocError::overflow("sum of invalid ids"))?;
+        }
+    }
+    Ok(Answer::int(sum))
+}
+
+fn part1_reference(ranges: &[(u128, u128)], _params: &Params) -> Result<Answer, AocError> {
+    sum_repeated_reference(ranges, true)
+}
+
+fn part2_reference(ranges: &[(u128, u128)], _params: &Params) -> Result<Answer, AocError> {
+    sum_repeated_reference(ranges, false)
+}
+
+/// `size` ranges of ids up to 10 digits long and a few thousand wide
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let ranges: Vec<String> = (0..size).map(|_| {
+        let digits = rng.between(1, 10) as u32;
+        let start = rng.between(10u64.pow(digits - 1), 10u64.pow(digits) - 1);
+        format!("{start}-{}", start + rng.below(5000))
+    }).collect();
+    ranges.join(",") + "\n"
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u64 = 2;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "1227775554", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "4174379265", params: &[] }),
+    ];
+    const VARIANTS: &'static [Variant<Day2>] = &[
+        Variant { part: 1, name: "reference", solve: |ranges, params| part1_reference(ranges, params) },
+        Variant { part: 1, name: "every_id", solve: part1_every_id },
+        Variant { part: 1, name: "explain", solve: |ranges, params| part1_explain(ranges, params) },
+        Variant { part: 2, name: "reference", solve: |ranges, params| part2_reference(ranges, params) },
+        Variant { part: 2, name: "every_id", solve: part2_every_id },
+        Variant { part: 2, name: "explain", solve: |ranges, params| part2_explain(ranges, params) },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Vec<(u128, u128)>;
+
+    fn parse(input: &str) -> Result<Vec<(u128, u128)>, AocError> {
+        parse_ranges(input)
+    }
+
+    /// Sums the repeating ids in each range directly instead of checking every id
+    fn part1(ranges: &Vec<(u128, u128)>, _params: &Params) -> Result<Answer, AocError> {
+        let sum = ranges.iter().try_fold(0u128, |sum, &(start, end)| {
+            sum.checked_add(sum_invalid_part1(start, end)?).ok_or_else(|| AocError::overflow("sum of invalid ids"))
+        })?;
+        Ok(Answer::int(sum))
+    }
+
+    /// Sums the repeating ids in each range directly instead of checking every id
+    fn part2(ranges: &Vec<(u128, u128)>, _params: &Params) -> Result<Answer, AocError> {
+        let sum = ranges.iter().try_fold(0u128, |sum, &(start, end)| {
+            sum.checked_add(sum_invalid_part2(start, end)?).ok_or_else(|| AocError::overflow("sum of invalid ids"))
+        })?;
+        Ok(Answer::int(sum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic<'a>(answer: &'a Answer, name: &str) -> Option<&'a str> {
+        answer.diagnostics.iter().find(|(other, _)| other == name).map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn explains_a_wide_range_without_listing_it_all() {
+        let ranges = [(1, 9_999_999_999_999_999)];
+        for only_twice in [true, false] {
+            let answer = explain(&ranges, only_twice).unwrap();
+            let sum = match only_twice {
+                true => sum_invalid_part1(1, 9_999_999_999_999_999),
+                false => sum_invalid_part2(1, 9_999_999_999_999_999),
+            };
+            assert_eq!(answer.to_string(), sum.unwrap().to_string());
+            assert!(diagnostic(&answer, "listed").is_some());
+
+            let rows = answer.trace.unwrap().rows;
+            assert_eq!(rows.len(), EXPLAIN_LIMIT);
+            let ids: Vec<u128> = rows.iter().map(|row| row[1].parse().unwrap()).collect();
+            assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn counts_and_lists_every_id_once() {
+        let (start, end) = (1, 200_000);
+        for only_twice in [true, false] {
+            let expected: Vec<u128> = (start..=end).filter(|id| is_repeated_reference(*id, only_twice)).collect();
+            let answer = explain(&[(start, end)], only_twice).unwrap();
+            assert_eq!(diagnostic(&answer, "invalid ids"), Some(expected.len().to_string().as_str()));
+            assert_eq!(answer.to_string(), expected.iter().sum::<u128>().to_string());
+
+            let listed: Vec<u128> = repeating_ids(start, end, only_twice).map(|(id, _, _)| id).collect();
+            assert_eq!(listed, expected);
+        }
+        assert_eq!(repeating_ids(1111, 1111, false).collect::<Vec<_>>(), [(1111, 1, 4)]);
+    }
+}
@@ -1,24 +1,40 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::params::{Param, ParamKind, Params};
+use crate::days::{Example, Solution, Variant};
 
-fn sort_chars(line: &str) -> Result<Vec<(usize, u32)>, JsError> {
-    let mut option_vec: Option<Vec<(usize, u32)>> = line.chars().enumerate().map(|(i, c)| {
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+/// One bank's batteries, sorted by joltage (highest first) then position
+pub struct Bank {
+    line: String,
+    sorted: Vec<(usize, u32)>,
+}
+
+fn sort_chars(input: &str, line: &str) -> Result<Vec<(usize, u32)>, AocError> {
+    let mut vec: Vec<(usize, u32)> = line.char_indices().enumerate().map(|(i, (byte_idx, c))| {
         let digit = char::to_digit(c, 10);
         match digit {
-            Some(digit) => Some((i, digit as u32)),
-            None => None
+            Some(digit) => Ok((i, digit)),
+            None => Err(AocError::parse_at(input, &line[byte_idx..], format!("expected a digit, found {c:?}")))
         }
-    }).collect();
-
-    let mut vec: Vec<(usize, u32)> = option_vec.ok_or_else(|| JsError::new("Some characters did not parse correctly"))?;
+    }).collect::<Result<Vec<(usize, u32)>, AocError>>()?;
 
     vec.sort_by_key(|(i, c)| (*c, -(*i as i32)));
     vec.reverse();
     Ok(vec)
 }
 
-fn calc_joltage(line: &str, sum: u128, pick: (usize, u32), depth: usize, max_depth: usize, sorted: &Vec<(usize, u32)>) -> Result<Option<u128>, JsError> {
+fn calc_joltage(sum: u128, pick: (usize, u32), depth: usize, max_depth: usize, sorted: &Vec<(usize, u32)>) -> Result<Option<u128>, AocError> {
     let new_sum = sum.checked_mul(10).and_then(|x| x.checked_add(pick.1 as u128)).ok_or_else(
-        || JsError::new("Overflow")
+        || AocError::overflow("joltage")
     )?;
     if depth + 1 == max_depth {
         return Ok(Some(new_sum));
@@ -26,7 +42,7 @@ fn calc_joltage(line: &str, sum: u128, pick: (usize, u32), depth: usize, max_dep
 
     for item in sorted {
         if item.0 > pick.0 {
-            let find = calc_joltage(line, new_sum, *item, depth + 1, max_depth, sorted)?;
+            let find = calc_joltage(new_sum, *item, depth + 1, max_depth, sorted)?;
             match find {
                 Some(_find) => return Ok(find),
                 None => {}
@@ -37,12 +53,13 @@ fn calc_joltage(line: &str, sum: u128, pick: (usize, u32), depth: usize, max_dep
     Ok(None)
 }
 
-fn calc_joltage_initial(line: &str, max_depth: usize) -> Result<u128, JsError> {
-    let sorted = sort_chars(line)?;
-
-    let result: Option<Result<u128, JsError>> = sorted.iter().find_map(|item| {
+fn calc_joltage_initial(bank: &Bank, max_depth: usize) -> Result<u128, AocError> {
+    if max_depth == 0 {
+        return Err(AocError::invalid("each bank needs to turn on at least one battery"));
+    }
+    let result: Option<Result<u128, AocError>> = bank.sorted.iter().find_map(|item| {
 
-        let joltage = calc_joltage(line, 0, *item, 0, max_depth, &sorted);
+        let joltage = calc_joltage(0, *item, 0, max_depth, &bank.sorted);
         match joltage {
             Ok(Some(joltage)) => Some(Ok(joltage)),
             Ok(None) => None,
@@ -50,26 +67,97 @@ fn calc_joltage_initial(line: &str, max_depth: usize) -> Result<u128, JsError> {
         }
     });
 
-    result.ok_or_else(|| JsError::new("No result found for line"))?
+    result.ok_or_else(|| AocError::no_solution(format!("no joltage of length {max_depth} in {:?}", bank.line)))?
     
 }
 
-pub fn day3_part1(input: &str) -> Result<String, JsError> {
-    let joltage_sum: u128 = input.lines().filter(|line| {
-        line.trim().len() > 0
-    }).map(|line| {
-        calc_joltage_initial(line, 2)
-    }).sum::<Result<u128, JsError>>()?;
+/// The same joltage by dynamic programming instead of searching: `best[k]` is the
+/// largest joltage from turning on `k` of the batteries seen so far
+fn calc_joltage_reference(bank: &Bank, batteries: usize) -> Result<u128, AocError> {
+    if batteries == 0 {
+        return Err(AocError::invalid("each bank needs to turn on at least one battery"));
+    }
+    let mut best: Vec<Option<u128>> = vec![None; batteries + 1];
+    best[0] = Some(0);
+    for digit in bank.line.chars().filter_map(|c| c.to_digit(10)) {
+        for k in (1..=batteries).rev() {
+            if let Some(prev) = best[k - 1] {
+                let joltage = prev.checked_mul(10).and_then(|x| x.checked_add(digit as u128)).ok_or_else(
+                    || AocError::overflow("joltage")
+                )?;
+                best[k] = best[k].max(Some(joltage));
+            }
+        }
+    }
+    best[batteries].ok_or_else(|| AocError::no_solution(format!("no joltage of length {batteries} in {:?}", bank.line)))
+}
+
+fn sum_joltages_reference(banks: &[Bank], batteries: usize) -> Result<Answer, AocError> {
+    let joltage_sum = banks.iter().map(|bank| calc_joltage_reference(bank, batteries)).sum::<Result<u128, AocError>>()?;
+    Ok(Answer::int(joltage_sum))
+}
 
-    Ok(joltage_sum.to_string())
+fn part1_reference(banks: &[Bank], params: &Params) -> Result<Answer, AocError> {
+    sum_joltages_reference(banks, params.count("part1_batteries")?)
 }
 
-pub fn day3_part2(input: &str) -> Result<String, JsError> {
-    let joltage_sum: u128 = input.lines().filter(|line| {
-        line.trim().len() > 0
-    }).map(|line| {
-        calc_joltage_initial(line, 12)
-    }).sum::<Result<u128, JsError>>()?;
+fn part2_reference(banks: &[Bank], params: &Params) -> Result<Answer, AocError> {
+    sum_joltages_reference(banks, params.count("part2_batteries")?)
+}
 
-    Ok(joltage_sum.to_string())
+/// `size` banks of 100 batteries rated 1 to 9
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| {
+        let mut bank: String = (0..100).map(|_| char::from(b'0' + rng.between(1, 9) as u8)).collect();
+        bank.push('\n');
+        bank
+    }).collect()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u64 = 3;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "357", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "3121910778619", params: &[] }),
+    ];
+    const PARAMS: &'static [Param] = &[
+        Param { name: "part1_batteries", kind: ParamKind::Int, default: "2", description: "batteries turned on per bank in part 1" },
+        Param { name: "part2_batteries", kind: ParamKind::Int, default: "12", description: "batteries turned on per bank in part 2" },
+    ];
+    const VARIANTS: &'static [Variant<Day3>] = &[
+        Variant { part: 1, name: "reference", solve: |banks, params| part1_reference(banks, params) },
+        Variant { part: 2, name: "reference", solve: |banks, params| part2_reference(banks, params) },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Vec<Bank>;
+
+    fn parse(input: &str) -> Result<Vec<Bank>, AocError> {
+        input.lines().filter(|line| {
+            !line.trim().is_empty()
+        }).map(|line| {
+            Ok(Bank { line: line.to_string(), sorted: sort_chars(input, line)? })
+        }).collect()
+    }
+
+    fn part1(banks: &Vec<Bank>, params: &Params) -> Result<Answer, AocError> {
+        let batteries = params.count("part1_batteries")?;
+        let joltage_sum: u128 = banks.iter().map(|bank| {
+            calc_joltage_initial(bank, batteries)
+        }).sum::<Result<u128, AocError>>()?;
+
+        Ok(Answer::int(joltage_sum))
+    }
+
+    fn part2(banks: &Vec<Bank>, params: &Params) -> Result<Answer, AocError> {
+        let batteries = params.count("part2_batteries")?;
+        let joltage_sum: u128 = banks.iter().map(|bank| {
+            calc_joltage_initial(bank, batteries)
+        }).sum::<Result<u128, AocError>>()?;
+
+        Ok(Answer::int(joltage_sum))
+    }
 }
@@ -1,143 +1,146 @@
-use leptos::wasm_bindgen::JsError;
-
-struct Board {
-    items: Vec<char>,
-    num_rows: usize,
-    num_cols: usize,
-}
-
-impl Board {
-    fn get(&self, c: i32, r: i32) -> Option<char> {
-        let num_cols = self.num_cols as i32;
-        let num_rows = self.num_rows as i32;
-
-        if c < num_cols && r < num_rows && c >= 0 && r >= 0 {
-            let idx = (c as usize) + (r as usize * self.num_cols);
-            let item = self.items.get(idx);
-            item.and_then(|c| Some(*c))
-        } else {
-            None
-        }
-    }
-
-    fn get_mut(&mut self, c: i32, r: i32) -> Result<&mut char, JsError> {
-        let num_cols = self.num_cols as i32;
-        let num_rows = self.num_rows as i32;
-
-        if c < num_cols && r < num_rows && c >= 0 && r >= 0 {
-            let idx = (c as usize) + (r as usize * self.num_cols);
-            self.items.get_mut(idx).ok_or_else(|| JsError::new("out of bounds"))
-        } else {
-            Err(JsError::new("out of bounds"))
-        }
-    }
-}
-
-fn read_board(input: &str) -> Result<Board, JsError> {
-    let first_line: &str = input.lines().next().ok_or_else(
-        || JsError::new("Could not read first line")
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::grid::Grid;
+use crate::params::{Param, ParamKind, Params};
+use crate::days::{Example, Solution, Variant};
+
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+/// A roll is accessible when fewer than `neighbour_limit` of its neighbours are rolls
+fn is_accessible(board: &Grid<char>, c: i64, r: i64, neighbour_limit: usize) -> Result<bool, AocError> {
+    let center = board.get(c, r).ok_or_else(
+        || AocError::invalid(format!("({c}, {r}) is outside the board"))
     )?;
-    let num_cols = first_line.len();
-    if num_cols == 0 {
-        return Err(JsError::new("First line is empty"));
+    if *center != '@' {
+        return Ok(false);
     }
 
-    let mut items: Vec<char> = Vec::new();
-    let mut num_rows = 0;
-    for line in input.lines() {
-        if line.len() == 0 {
-            continue;
-        }
-
-        if line.len() != num_cols {
-            return Err(JsError::new("Line length does not match first line"));
-        }
+    let count = board.neighbours8(c, r).filter(|&(check_c, check_r)| board.get(check_c, check_r) == Some(&'@')).count();
+    Ok(count < neighbour_limit)
+}
 
-        for char in line.chars() {
-            items.push(char);
+fn count_accessible(board: &Grid<char>, neighbour_limit: usize) -> Result<usize, AocError> {
+    let mut count = 0;
+    for (c, r) in board.positions() {
+        if is_accessible(board, c, r, neighbour_limit)? {
+            count += 1;
         }
-        num_rows += 1;
     }
+    Ok(count)
+}
 
-    Ok(Board {
-        items,
-        num_rows,
-        num_cols,
-    })
+/// Rolls with fewer than `neighbour_limit` rolls among the eight squares around
+/// them, counted square by square
+fn accessible_reference(board: &Grid<char>, neighbour_limit: usize) -> Vec<(i64, i64)> {
+    board.positions().filter(|&(c, r)| {
+        let rolls = (-1..=1).flat_map(|dr| (-1..=1).map(move |dc| (dc, dr)))
+            .filter(|&(dc, dr)| (dc, dr) != (0, 0) && board.get(c + dc, r + dr) == Some(&'@'))
+            .count();
+        board.get(c, r) == Some(&'@') && rolls < neighbour_limit
+    }).collect()
 }
 
-fn is_accessible(board: &Board, c: i32, r: i32) -> Result<bool, JsError> {
-    let center = board.get(c, r);
-    match center {
-        Some(_center) => {
-            if _center != '@' {
-                return Ok(false);
-            }
-        }
-        None => {
-            return Err(
-                JsError::new("unexpected error, checking empty space")
-            );
-        }
-    }
+fn part1_reference(board: &Grid<char>, params: &Params) -> Result<Answer, AocError> {
+    Ok(Answer::int(accessible_reference(board, params.count("neighbour_limit")?).len() as u128))
+}
 
+/// Removes every accessible roll at once, round after round. Removing a roll only
+/// ever frees up others, so this ends with the same rolls gone as removing one at a time
+fn part2_reference(board: &Grid<char>, params: &Params) -> Result<Answer, AocError> {
+    let neighbour_limit = params.count("neighbour_limit")?;
+    let mut board = board.clone();
     let mut count = 0;
-    for check_r in r - 1..=r + 1 {
-        for check_c in c - 1..=c + 1 {
-            if check_r == r && check_c == c {
-                continue;
-            }
-            let item = board.get(check_c, check_r);
-            match item {
-                Some(_item) => {
-                    if _item == '@' {
-                        count += 1;
-                    }
-                },
-                None => {
-
-                }
+    loop {
+        let accessible = accessible_reference(&board, neighbour_limit);
+        if accessible.is_empty() {
+            return Ok(Answer::int(count as u128));
+        }
+        count += accessible.len();
+        for (c, r) in accessible {
+            if let Some(item) = board.get_mut(c, r) {
+                *item = '.';
             }
         }
     }
-    Ok(count < 4)
 }
 
-fn count_accessible(board: &Board) -> Result<usize, JsError> {
-    let mut count = 0;
-    for r in 0..board.num_rows {
-        for c in 0..board.num_cols {
-            if is_accessible(&board, c as i32, r as i32)? {
-                count += 1;
-            }
+/// A `size` by `size` floor about two thirds covered in rolls
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut board = Grid::new(size, size, '.');
+    for (c, r) in board.positions() {
+        if rng.chance(65) && let Some(item) = board.get_mut(c, r) {
+            *item = '@';
         }
     }
-    Ok(count)
+    board.to_string()
 }
 
-pub fn day4_part1(input: &str) -> Result<String, JsError> {
-    let board = read_board(input)?;
-
-    let count = count_accessible(&board)?;
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u64 = 4;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "13", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "43", params: &[] }),
+    ];
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "neighbour_limit",
+            kind: ParamKind::Int,
+            default: "4",
+            description: "a roll is accessible with fewer than this many neighbouring rolls",
+        },
+    ];
+    const VARIANTS: &'static [Variant<Day4>] = &[
+        Variant { part: 1, name: "reference", solve: part1_reference },
+        Variant { part: 2, name: "reference", solve: part2_reference },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Grid<char>;
+
+    fn parse(input: &str) -> Result<Grid<char>, AocError> {
+        Grid::parse(input)
+    }
 
-    Ok(count.to_string())
-}
+    fn part1(board: &Grid<char>, params: &Params) -> Result<Answer, AocError> {
+        let count = count_accessible(board, params.count("neighbour_limit")?)?;
 
-pub fn day4_part2(input: &str) -> Result<String, JsError> {
-    let mut board = read_board(input)?;
+        Ok(Answer::int(count as u128))
+    }
 
-    let mut count = 0;
-    while count_accessible(&board)? > 0 {
-        for r in 0..board.num_rows {
-            for c in 0..board.num_cols {
-                if is_accessible(&board, c as i32, r as i32)? {
-                    let ref_item = board.get_mut(c as i32, r as i32)?;
+    fn part2(board: &Grid<char>, params: &Params) -> Result<Answer, AocError> {
+        let neighbour_limit = params.count("neighbour_limit")?;
+        let mut board = board.clone();
+
+        let mut count = 0;
+        while count_accessible(&board, neighbour_limit)? > 0 {
+            for (c, r) in board.positions() {
+                if is_accessible(&board, c, r, neighbour_limit)? {
+                    let ref_item = board.get_mut(c, r).ok_or_else(
+                        || AocError::invalid(format!("({c}, {r}) is out of bounds"))
+                    )?;
                     *ref_item = 'x';
                     count += 1;
                 }
             }
         }
-    }
 
-    Ok(count.to_string())
+        Ok(Answer::int(count as u128))
+    }
 }
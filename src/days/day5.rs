@@ -1,52 +1,47 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::parse;
+use crate::params::Params;
+use crate::days::{Example, Solution, Variant};
 
-fn parse_ranges(lines: &mut std::str::Lines) -> Result<Vec<std::ops::Range<u64>>, JsError> {
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
+pub struct Inventory {
+    ranges: Vec<std::ops::Range<u64>>,
+    ingredients: Vec<u64>,
+}
+
+fn parse_ranges(input: &str, lines: &mut std::str::Lines) -> Result<Vec<std::ops::Range<u64>>, AocError> {
     lines.take_while(|line| {
         !line.trim().is_empty()
     }).map(|line| {
-        let mut split = line.trim().split("-");
-        let start: u64 = split.next().ok_or_else(|| {
-            JsError::new("Could not read start")
-        }).and_then(|s| {
-            Ok(str::parse::<u64>(s)?)
-        })?;
-        let end: u64 = split.next().ok_or_else(|| {
-            JsError::new("Could not read start")
-        }).and_then(|s| {
-            Ok(str::parse::<u64>(s)?)
-        })?;
-        
+        let (start, end): (u64, u64) = parse::range(input, line, "an id")?;
         Ok(std::ops::Range {
             start,
-            end: end + 1,
+            end: end.checked_add(1).ok_or_else(|| AocError::overflow("range end"))?,
         })
     }).collect()
 }
 
-fn parse_ingredients(lines: &mut std::str::Lines) -> Result<Vec<u64>, JsError> {
-    lines.map(|line| {
-        match str::parse::<u64>(line) {
-            Ok(_line) => Ok(_line),
-            Err(_err) => Err(_err.into())
-        }
-    }).collect()
-}
-
-pub fn day5_part1(input: &str) -> Result<String, JsError> {
-    let mut lines = input.lines();
-    let ranges = parse_ranges(&mut lines)?;
-    let ingredients = parse_ingredients(&mut lines)?;
-    
-    let count = ingredients.iter().filter_map(|ingredient| {
-        ranges.iter().find(|range| {
-            range.contains(ingredient)
-        })
-    }).count();
-    
-    Ok(count.to_string())
+fn parse_ingredients(input: &str, lines: &mut std::str::Lines) -> Result<Vec<u64>, AocError> {
+    lines.filter(|line| !line.trim().is_empty()).map(|line| parse::int(input, line, "an ingredient id")).collect()
 }
 
-fn deoverlap(range: &Vec<std::ops::Range<u64>>) -> Result<Vec<std::ops::Range<u64>>, JsError> {
+fn deoverlap(range: &Vec<std::ops::Range<u64>>) -> Result<Vec<std::ops::Range<u64>>, AocError> {
     let mut clone: Vec<std::ops::Range<u64>> = range.clone();
     clone.sort_by_key(|range| { range.start });
     
@@ -74,16 +69,84 @@ fn deoverlap(range: &Vec<std::ops::Range<u64>>) -> Result<Vec<std::ops::Range<u6
     Ok(copy)
 }
 
-pub fn day5_part2(input: &str) -> Result<String, JsError> {
-    let mut lines = input.lines();
-    let ranges = parse_ranges(&mut lines)?;
+/// Sweeps along the ids from one range end to the next, counting the stretches
+/// covered by at least one range, without merging ranges first
+fn part2_reference(inventory: &Inventory, _params: &Params) -> Result<Answer, AocError> {
+    let mut ends: Vec<(u64, i64)> = inventory.ranges.iter().flat_map(|range| [(range.start, 1), (range.end, -1)]).collect();
+    ends.sort();
 
-    let nonoverlapping = deoverlap(&ranges)?;
-    
-    let sum: u64 = nonoverlapping.iter().map(|range| {
-        range.end - range.start
-    }).sum();
-    
-    Ok(sum.to_string())
+    let (mut fresh, mut covering, mut prev) = (0u128, 0i64, 0u64);
+    for (id, change) in ends {
+        if covering > 0 {
+            fresh += (id - prev) as u128;
+        }
+        covering += change;
+        prev = id;
+    }
+    Ok(Answer::int(fresh))
 }
 
+/// `size` fresh ranges, then `size` ingredient ids of which about half are fresh
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let ranges: Vec<(u64, u64)> = (0..size).map(|_| {
+        let start = rng.between(1, 500_000_000_000_000);
+        (start, start + rng.below(10_000_000_000_000))
+    }).collect();
+
+    let mut input: String = ranges.iter().map(|(start, end)| format!("{start}-{end}\n")).collect();
+    input.push('\n');
+    for _ in 0..size {
+        let id = if rng.chance(50) {
+            let (start, end) = *rng.pick(&ranges);
+            rng.between(start, end)
+        } else {
+            rng.between(1, 510_000_000_000_000)
+        };
+        input.push_str(&format!("{id}\n"));
+    }
+    input
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u64 = 5;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "3", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "14", params: &[] }),
+    ];
+    const VARIANTS: &'static [Variant<Day5>] = &[
+        Variant { part: 2, name: "reference", solve: part2_reference },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Inventory;
+
+    fn parse(input: &str) -> Result<Inventory, AocError> {
+        let mut lines = input.lines();
+        let ranges = parse_ranges(input, &mut lines)?;
+        let ingredients = parse_ingredients(input, &mut lines)?;
+        Ok(Inventory { ranges, ingredients })
+    }
+
+    fn part1(inventory: &Inventory, _params: &Params) -> Result<Answer, AocError> {
+        let count = inventory.ingredients.iter().filter_map(|ingredient| {
+            inventory.ranges.iter().find(|range| {
+                range.contains(ingredient)
+            })
+        }).count();
+
+        Ok(Answer::int(count as u128))
+    }
+
+    fn part2(inventory: &Inventory, _params: &Params) -> Result<Answer, AocError> {
+        let nonoverlapping = deoverlap(&inventory.ranges)?;
+
+        let sum: u64 = nonoverlapping.iter().map(|range| {
+            range.end - range.start
+        }).sum();
+
+        Ok(Answer::int(sum as u128))
+    }
+}
@@ -1,34 +1,57 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::params::Params;
+use crate::days::{Example, Solution, Variant};
+
+/// Worked example from the puzzle text. Written out line by line because the
+/// trailing spaces matter to `parse_math_part2`
+pub const EXAMPLE: &str = concat!(
+    "123 328  51 64 \n",
+    " 45 64  387 23 \n",
+    "  6 98  215 314\n",
+    "*   +   *   +  \n",
+);
 
 struct Math {
     math: Vec<(Vec<u128>, char)>,
 }
 
-fn parse_math_part1(input: &str) -> Result<Math, JsError> {
+/// The worksheet read both ways, row by row for part 1 and column by column for part 2.
+/// Each reading keeps its own error, so a worksheet only one part can read (e.g. with
+/// its trailing spaces trimmed, which part 2 needs) still solves the other
+pub struct Worksheet {
+    rows: Result<Math, AocError>,
+    columns: Result<Math, AocError>,
+    /// The worksheet as written, which the reference variants read for themselves
+    lines: Vec<Vec<char>>,
+}
+
+fn parse_math_part1(input: &str) -> Result<Math, AocError> {
     let line_count = input.lines().count();
 
     if line_count == 0 {
-        return Err(JsError::new("No lines found"));
+        return Err(AocError::invalid("No lines found"));
     }
 
-    let numbers_result: Result<Vec<Vec<u128>>, JsError> = input.lines().take(line_count - 1).map(|line| {
-        let line_numbers: Result<Vec<u128>, JsError> = line.split_whitespace().map(|piece| {
+    let numbers_result: Result<Vec<Vec<u128>>, AocError> = input.lines().take(line_count - 1).map(|line| {
+        let line_numbers: Result<Vec<u128>, AocError> = line.split_whitespace().map(|piece| {
             match str::parse::<u128>(piece) {
                 Ok(_parsed) => Ok(_parsed),
-                Err(_err) => Err(_err.into())
+                Err(_err) => Err(AocError::parse_at(input, piece, format!("invalid number {piece:?}: {_err}")))
             }
         }).collect();
         line_numbers
     }).collect();
 
-    let final_line  = input.lines().skip(line_count - 1).next().ok_or_else(|| JsError::new("No final line found"))?;
-    let operators_result: Result<Vec<char>, JsError> = final_line.split_whitespace().map(
+    let final_line  = input.lines().skip(line_count - 1).next().ok_or_else(|| AocError::invalid("No final line found"))?;
+    let operators_result: Result<Vec<char>, AocError> = final_line.split_whitespace().map(
         |piece| {
             if piece.len() > 1 {
-                Err(JsError::new("Operators should be only one character long"))
+                Err(AocError::parse_at(input, piece, "Operators should be only one character long"))
             } else {
                 piece.chars().next().ok_or_else(
-                    || JsError::new("No operator found")
+                    || AocError::parse_at(input, piece, "No operator found")
                 )
             }
         }
@@ -38,9 +61,11 @@ fn parse_math_part1(input: &str) -> Result<Math, JsError> {
     let operators = operators_result?;
 
     let column_count = operators.len();
-    for row in numbers.iter() {
+    for (row_idx, row) in numbers.iter().enumerate() {
         if row.len() != column_count {
-            return Err(JsError::new("All rows should have the same number of columns"));
+            return Err(AocError::parse(
+                row_idx + 1, 0, format!("found {} columns but there are {column_count} operators", row.len())
+            ));
         }
     }
 
@@ -48,7 +73,7 @@ fn parse_math_part1(input: &str) -> Result<Math, JsError> {
     for (row_idx, row) in numbers.iter().enumerate() {
         for (col_idx, col) in row.iter().enumerate() {
             let new_col = transposed_numbers.get_mut(col_idx).ok_or_else(
-                || JsError::new("Could not get column")
+                || AocError::invalid("Could not get column")
             )?;
             new_col.push(*col);
         }
@@ -61,17 +86,17 @@ fn parse_math_part1(input: &str) -> Result<Math, JsError> {
     })
 }
 
-fn parse_math_part2(input: &str) -> Result<Math, JsError> {
+fn parse_math_part2(input: &str) -> Result<Math, AocError> {
     let line_count = input.lines().count();
+    if line_count == 0 {
+        return Err(AocError::invalid("No lines found"));
+    }
+
     let number_lines: Vec<Vec<char>> = input.lines().take(line_count - 1).map(
         |line| line.chars().collect()
     ).collect();
 
-    if line_count == 0 {
-        return Err(JsError::new("No lines found"));
-    }
-
-    let final_line  = input.lines().skip(line_count - 1).next().ok_or_else(|| JsError::new("No final line found"))?;
+    let final_line  = input.lines().skip(line_count - 1).next().ok_or_else(|| AocError::invalid("No final line found"))?;
     let operators: Vec<(usize, char)> = final_line.chars().enumerate().filter_map(
         |(idx, piece)| {
             if piece != ' ' {
@@ -82,7 +107,7 @@ fn parse_math_part2(input: &str) -> Result<Math, JsError> {
         }
     ).collect();
 
-    let operator_pairs_result: Result<Vec<((usize, char), usize)>, JsError> = operators.windows(2).map(|pair| {
+    let operator_pairs_result: Result<Vec<((usize, char), usize)>, AocError> = operators.windows(2).map(|pair| {
         let first = pair.get(0).and_then(
             |item| Some(*item)
         );
@@ -96,12 +121,12 @@ fn parse_math_part2(input: &str) -> Result<Math, JsError> {
                         Ok((_first, _second))
                     },
                     None => {
-                        Err(JsError::new("unable to find second item"))
+                        Err(AocError::invalid("unable to find second item"))
                     }
                 }
             },
             None => {
-                Err(JsError::new("unable to find first item"))
+                Err(AocError::invalid("unable to find first item"))
             }
         }
     }).chain([
@@ -110,20 +135,20 @@ fn parse_math_part2(input: &str) -> Result<Math, JsError> {
             Some(_last) => {
                 Ok((*_last, final_line.len()))
             },
-            None => Err(JsError::new("unable to find last")),
+            None => Err(AocError::parse(line_count, 0, "No operators found")),
         }
     ]).collect();
     let operator_pairs = operator_pairs_result?;
 
-    let numbers_result: Result<Vec<(Vec<u128>, char)>, JsError> = operator_pairs.iter().map(|pair| {
+    let numbers_result: Result<Vec<(Vec<u128>, char)>, AocError> = operator_pairs.iter().map(|pair| {
         let mut num_vec: Vec<u128> = vec![0; pair.1 - pair.0.0];
 
-        for line in number_lines.iter() {
+        for (line_idx, line) in number_lines.iter().enumerate() {
             for idx in 0..(pair.1 - pair.0.0) {
                 let col_idx = idx + pair.0.0;
 
                 let c = line.get(col_idx).ok_or_else(
-                    || JsError::new("No char found")
+                    || AocError::parse(line_idx + 1, col_idx + 1, "line is shorter than the operator line")
                 )?;
                 match c {
                     ' ' => {
@@ -133,14 +158,14 @@ fn parse_math_part2(input: &str) -> Result<Math, JsError> {
                         match char::to_digit(*c, 10) {
                             Some(_digit) => {
                                 let item = num_vec.get_mut(idx).ok_or_else(
-                                    || JsError::new("can't access item")
+                                    || AocError::invalid("can't access item")
                                 )?;
 
                                 *item = (*item * 10) + _digit as u128;
                             },
                             None => {
                                 Err(
-                                    JsError::new("Unknown char")
+                                    AocError::parse(line_idx + 1, col_idx + 1, format!("expected a digit or space, found {c:?}"))
                                 )?
                             }
                         }
@@ -158,37 +183,148 @@ fn parse_math_part2(input: &str) -> Result<Math, JsError> {
     })
 }
 
-fn calc_math(math: &Math) -> Result<u128, JsError> {
+fn calc_math(math: &Math) -> Result<u128, AocError> {
     math.math.iter().map(|(_numbers, op)| {
         match op {
             '*' => _numbers.iter().try_fold(1u128, |acc, item| {
                 acc.checked_mul(*item).ok_or_else(
-                    || JsError::new("Overflow in mul")
+                    || AocError::overflow("product of column")
                 )
             }),
             '+' => _numbers.iter().try_fold(0u128, |acc, item| {
                 acc.checked_add(*item).ok_or_else(
-                    || JsError::new("Overflow in sum")
+                    || AocError::overflow("sum of column")
                 )
             }),
-            _ => Err(JsError::new("Unknown operator"))
+            _ => Err(AocError::invalid(format!("Unknown operator {op:?}")))
         }
     }).try_fold(0u128, |acc, item| {
         item.and_then(|_item| _item.checked_add(acc).ok_or_else(
-            || JsError::new("Overflow in sum total")
+            || AocError::overflow("sum total")
         ))
     })
 }
 
-pub fn day6_part1(input: &str) -> Result<String, JsError> {
-    let math = parse_math_part1(input)?;
-    let total = calc_math(&math)?;
-    Ok(total.to_string())
+/// Problems as (start, end) character columns, split at columns that are blank on
+/// every line rather than by where the operators are
+fn problem_columns_reference(lines: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let blank = |col: usize| lines.iter().all(|line| line.get(col).is_none_or(|c| *c == ' '));
+    let mut problems = Vec::new();
+    let mut start = None;
+    for col in 0..=width {
+        match (start, col == width || blank(col)) {
+            (Some(first), true) => {
+                problems.push((first, col));
+                start = None;
+            }
+            (None, false) => start = Some(col),
+            _ => {}
+        }
+    }
+    problems
+}
+
+/// Adds up every problem, with `numbers` reading one problem's numbers out of its
+/// columns of the number lines
+fn solve_reference(
+    worksheet: &Worksheet,
+    numbers: fn(&[Vec<char>], usize, usize) -> Vec<u128>,
+) -> Result<Answer, AocError> {
+    let (operators, number_lines) = worksheet.lines.split_last().ok_or_else(|| AocError::invalid("No lines found"))?;
+    let mut total: u128 = 0;
+    for (start, end) in problem_columns_reference(&worksheet.lines) {
+        let operator = operators.get(start..end.min(operators.len())).and_then(|ops| ops.iter().find(|c| **c != ' '));
+        let numbers = numbers(number_lines, start, end);
+        let result = match operator {
+            Some('+') => numbers.iter().try_fold(0u128, |acc, num| acc.checked_add(*num)),
+            Some('*') => numbers.iter().try_fold(1u128, |acc, num| acc.checked_mul(*num)),
+            _ => return Err(AocError::invalid(format!("No operator under columns {} to {end}", start + 1))),
+        };
+        total = result.and_then(|result| total.checked_add(result)).ok_or_else(|| AocError::overflow("sum total"))?;
+    }
+    Ok(Answer::int(total))
 }
 
-pub fn day6_part2(input: &str) -> Result<String, JsError> {
-    let math = parse_math_part2(input)?;
-    let total = calc_math(&math)?;
-    Ok(total.to_string())
+/// Reads the digits from `chars` as one number, ignoring spaces
+fn read_number_reference<'a>(chars: impl Iterator<Item = &'a char>) -> Option<u128> {
+    chars.filter_map(|c| c.to_digit(10)).fold(None, |acc, digit| Some(acc.unwrap_or(0) * 10 + digit as u128))
 }
 
+fn part1_reference(worksheet: &Worksheet, _params: &Params) -> Result<Answer, AocError> {
+    solve_reference(worksheet, |lines, start, end| {
+        lines.iter().filter_map(|line| read_number_reference(line.get(start..end.min(line.len()))?.iter())).collect()
+    })
+}
+
+fn part2_reference(worksheet: &Worksheet, _params: &Params) -> Result<Answer, AocError> {
+    solve_reference(worksheet, |lines, start, end| {
+        (start..end).filter_map(|col| read_number_reference(lines.iter().filter_map(|line| line.get(col)))).collect()
+    })
+}
+
+/// `size` problems of three or four numbers, each problem's numbers lined up on
+/// the left or the right of its column as in the puzzle
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let num_rows = rng.between(3, 4) as usize;
+    let mut lines = vec![String::new(); num_rows + 1];
+    for problem in 0..size.max(1) {
+        if problem > 0 {
+            for line in lines.iter_mut() {
+                line.push(' ');
+            }
+        }
+        let numbers: Vec<String> = (0..num_rows).map(|_| {
+            let digits = rng.between(1, 4) as u32;
+            rng.between(10u64.pow(digits - 1), 10u64.pow(digits) - 1).to_string()
+        }).collect();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+        let left = rng.chance(50);
+        for (line, number) in lines.iter_mut().zip(&numbers) {
+            if left {
+                line.push_str(&format!("{number:<width$}"));
+            } else {
+                line.push_str(&format!("{number:>width$}"));
+            }
+        }
+        let operator = if rng.chance(50) { '+' } else { '*' };
+        lines[num_rows].push_str(&format!("{operator:<width$}"));
+    }
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u64 = 6;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "4277556", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "3263827", params: &[] }),
+    ];
+    const VARIANTS: &'static [Variant<Day6>] = &[
+        Variant { part: 1, name: "reference", solve: part1_reference },
+        Variant { part: 2, name: "reference", solve: part2_reference },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Worksheet;
+
+    fn parse(input: &str) -> Result<Worksheet, AocError> {
+        Ok(Worksheet {
+            rows: parse_math_part1(input),
+            columns: parse_math_part2(input),
+            lines: input.lines().map(|line| line.chars().collect()).collect(),
+        })
+    }
+
+    fn part1(worksheet: &Worksheet, _params: &Params) -> Result<Answer, AocError> {
+        let total = calc_math(worksheet.rows.as_ref().map_err(AocError::clone)?)?;
+        Ok(Answer::int(total))
+    }
+
+    fn part2(worksheet: &Worksheet, _params: &Params) -> Result<Answer, AocError> {
+        let total = calc_math(worksheet.columns.as_ref().map_err(AocError::clone)?)?;
+        Ok(Answer::int(total))
+    }
+}
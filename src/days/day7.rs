@@ -1,122 +1,72 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::grid::Grid;
+use crate::params::Params;
+use crate::days::{Example, Solution, Variant};
 
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
 
-#[derive(Clone)]
-struct Board {
-    items: Vec<char>,
-    num_rows: usize,
-    num_cols: usize,
-}
-
-impl Board {
-    fn get(&self, c: i32, r: i32) -> Option<char> {
-        let num_cols = self.num_cols as i32;
-        let num_rows = self.num_rows as i32;
-
-        if c < num_cols && r < num_rows && c >= 0 && r >= 0 {
-            let idx = (c as usize) + (r as usize * self.num_cols);
-            let item = self.items.get(idx);
-            item.and_then(|c| Some(*c))
-        } else {
-            None
-        }
-    }
+/// A square of the board as the grid's (col, row), so a beam split off the edge is
+/// just out of bounds
+type Position = (i64, i64);
 
-    fn get_mut(&mut self, c: i32, r: i32) -> Option<&mut char> {
-        let num_cols = self.num_cols as i32;
-        let num_rows = self.num_rows as i32;
-
-        if c < num_cols && r < num_rows && c >= 0 && r >= 0 {
-            let idx = (c as usize) + (r as usize * self.num_cols);
-            self.items.get_mut(idx)
-        } else {
-            None
-        }
-    }
-}
-
-fn parse_board(input: &str) -> Result<Board, JsError> {
-    let grid: Vec<char> = input.lines().flat_map(|line| {
-        line.chars()
-    }).collect();
+/// Follows the beams down row by row, keeping them in their own grid so a beam
+/// split onto a neighbouring splitter carries on down past it like the reference
+fn calc_splits_part1(board: &Grid<char>) -> Result<usize, AocError> {
+    let mut beams = Grid::new(board.num_cols(), board.num_rows(), false);
+    let mut num_splits = 0;
 
-    let mut num_cols = None;
-    for line in input.lines() {
-        if let Some(_num_cols) = num_cols {
-            if _num_cols != line.len() {
-                return Err(JsError::new("number of columns doesn't match"));
+    for ((col, row), piece) in board.iter() {
+        match piece {
+            'S' | '|' => {}
+            '.' | '^' => {
+                if beams.get(col, row) != Some(&true) {
+                    continue;
+                }
+            },
+            _piece => {
+                return Err(AocError::parse(row as usize + 1, col as usize + 1, format!("unexpected character {_piece:?}")));
             }
-        } else {
-            num_cols = Some(line.len());
         }
-    }
-
-    match num_cols {
-        Some(num_cols) => {
-            Ok(Board {
-                items: grid,
-                num_rows: input.lines().count(),
-                num_cols: num_cols,
-            })
-        },
-        None => return Err(JsError::new("no columns found")),
-    }
-}
-
-#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-struct Position {
-    row: usize,
-    col: usize
-}
-
-fn calc_splits_part1(board: &Board) -> Result<usize, JsError> {
-    let mut _board = board.clone();
-    let mut num_splits = 0;
 
-    for row in 0..board.num_rows {
-        for col in 0..board.num_cols {
-            match _board.get(col as i32, row as i32) {
-                Some('S' | '|') => {
-                    match _board.get_mut(col as i32, (row + 1) as i32) {
-                        Some('^') => {
-                            num_splits += 1;
-                            if let Some(_to_mut) = _board.get_mut((col - 1) as i32, (row + 1) as i32) {
-                                if *_to_mut != '.' && *_to_mut != '|' {
-                                    return Err(JsError::new("not an empty space"));
-                                }
-                                *_to_mut = '|';
-                            }
-
-                            if let Some(_to_mut) = _board.get_mut((col + 1) as i32, (row + 1) as i32) {
-                                if *_to_mut != '.' && *_to_mut != '|' {
-                                    return Err(JsError::new("not an empty space"));
-                                }
-                                *_to_mut = '|';
-                            }
-                        },
-                        Some(_next_piece) => {
-                            if *_next_piece == '.' {
-                                *_next_piece = '|';
-                            } else if *_next_piece == '|' {
-
-                            } else {
-                                return Err(JsError::new("unexpected piece"));
-                            }
-                        },
-                        None => {
-                            // end of board
-                        }
+        match board.get(col, row + 1) {
+            Some('^') => {
+                num_splits += 1;
+                for side in [col - 1, col + 1] {
+                    if let Some(beam) = beams.get_mut(side, row + 1) {
+                        *beam = true;
                     }
-                },
-                Some('.' | '^') => {
-
-                },
-                Some(_) => {
-                    return Err(JsError::new("unexpected character in current row"));
-                },
-                None => {
-                    return Err(JsError::new("unexpected end of board"));
                 }
+            },
+            Some('.' | '|') => {
+                if let Some(beam) = beams.get_mut(col, row + 1) {
+                    *beam = true;
+                }
+            },
+            Some(_next_piece) => {
+                return Err(AocError::parse(row as usize + 2, col as usize + 1, format!("unexpected character {_next_piece:?}")));
+            },
+            None => {
+                // end of board
             }
         }
     }
@@ -124,33 +74,19 @@ fn calc_splits_part1(board: &Board) -> Result<usize, JsError> {
     Ok(num_splits)
 }
 
-
-pub fn day7_part1(input: &str) -> Result<String, JsError> {
-    let board = parse_board(input)?;
-    let num_splits = calc_splits_part1(&board)?;
-
-    Ok(num_splits.to_string())
+fn calc_start(board: &Grid<char>) -> Option<Position> {
+    board.find(&'S')
 }
 
-fn calc_start(board: &Board) -> Option<Position> {
-    for row in 0..board.num_rows {
-        for col in 0..board.num_cols {
-            match board.get(col as i32, row as i32) {
-                Some('S') => {
-                    return Some(Position { row, col });
-                },
-                _ => {}
-            }
-        }
-    }
-    None
-}
-
-fn calc_splits_part2(board: &Board, position: Position, known: &mut std::collections::HashMap<Position, u128>) -> Result<u128, JsError> {
-    match board.get(position.col as i32, (position.row + 1) as i32) {
+/// Counts the splits below `position`, plus one for the timeline already there.
+/// A beam split off the side of the board finds nothing below it, so like one
+/// reaching the bottom it's a finished timeline
+fn calc_splits_part2(board: &Grid<char>, position: Position, known: &mut std::collections::HashMap<Position, u128>) -> Result<u128, AocError> {
+    let (col, row) = position;
+    match board.get(col, row + 1).copied() {
         Some('^') => {
-            let left_position = Position { row: position.row + 1, col: position.col - 1 };
-            let right_position = Position { row: position.row + 1, col: position.col + 1 };
+            let left_position = (col - 1, row + 1);
+            let right_position = (col + 1, row + 1);
 
             let left = if let Some(left_item) = known.get(&left_position) {
                 *left_item
@@ -176,10 +112,10 @@ fn calc_splits_part2(board: &Board, position: Position, known: &mut std::collect
                 right_item
             };
 
-            Ok(1 + left + right)
+            left.checked_add(right).and_then(|both| both.checked_add(1)).ok_or_else(|| AocError::overflow("number of timelines"))
         },
         Some('.') => {
-            let center_position = Position { row: position.row + 1, col: position.col };
+            let center_position = (col, row + 1);
 
             let center = if let Some(center_item) = known.get(&center_position) {
                 *center_item
@@ -195,8 +131,8 @@ fn calc_splits_part2(board: &Board, position: Position, known: &mut std::collect
 
             Ok(center)
         },
-        Some(_) => {
-            Err(JsError::new("unexpected character in next row"))
+        Some(_piece) => {
+            Err(AocError::parse((row + 2) as usize, (col + 1) as usize, format!("unexpected character {_piece:?}")))
         }
         None => {
             // end of board
@@ -204,13 +140,117 @@ fn calc_splits_part2(board: &Board, position: Position, known: &mut std::collect
         }
     }
 }
-pub fn day7_part2(input: &str) -> Result<String, JsError> {
-    let board = parse_board(input)?;
-
-    let start = calc_start(&board).ok_or_else(
-        || JsError::new("No start found")
-    )?;
-    let mut known: std::collections::HashMap<Position, u128> = std::collections::HashMap::new();
-    let num_splits = 1 + calc_splits_part2(&board, start, &mut known)?;
-    Ok(num_splits.to_string())
-}
\ No newline at end of file
+
+/// Follows the beams down one row at a time, keeping how many timelines are in
+/// each column, instead of recursing from the start. Returns the number of
+/// splitters hit and the number of timelines that reach the bottom or leave the
+/// sides. A count too big for a u128 stays at the most it can hold, which still
+/// tells where the beams go, so only the number of timelines is an overflow
+fn follow_beams_reference(board: &Grid<char>) -> Result<(u128, Result<u128, AocError>), AocError> {
+    let (start_col, start_row) = board.find(&'S').ok_or_else(|| AocError::invalid("No start 'S' found"))?;
+    let width = board.num_cols() as i64;
+    let mut timelines = vec![0u128; board.num_cols()];
+    timelines[start_col as usize] = 1;
+    let (mut splits, mut finished) = (0u128, 0u128);
+    let mut overflowed = false;
+    let mut add = |total: &mut u128, count: u128| match total.checked_add(count) {
+        Some(sum) => *total = sum,
+        None => (*total, overflowed) = (u128::MAX, true),
+    };
+
+    for row in start_row + 1..board.num_rows() as i64 {
+        let mut next = vec![0u128; board.num_cols()];
+        for (col, count) in timelines.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let col = col as i64;
+            if board.get(col, row) != Some(&'^') {
+                add(&mut next[col as usize], *count);
+                continue;
+            }
+            splits += 1;
+            for side in [col - 1, col + 1] {
+                if (0..width).contains(&side) {
+                    add(&mut next[side as usize], *count);
+                } else {
+                    add(&mut finished, *count);
+                }
+            }
+        }
+        timelines = next;
+    }
+    for count in timelines {
+        add(&mut finished, count);
+    }
+    let finished = if overflowed { Err(AocError::overflow("number of timelines")) } else { Ok(finished) };
+    Ok((splits, finished))
+}
+
+fn part1_reference(board: &Grid<char>, _params: &Params) -> Result<Answer, AocError> {
+    Ok(Answer::int(follow_beams_reference(board)?.0))
+}
+
+fn part2_reference(board: &Grid<char>, _params: &Params) -> Result<Answer, AocError> {
+    Ok(Answer::int(follow_beams_reference(board)?.1?))
+}
+
+/// A manifold with `size` rows of splitters, one every other row. Most rows keep
+/// them apart and off the edge like the puzzle's, but some are crowded, with
+/// splitters side by side and against the sides
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let width = 2 * size + 1;
+    let mut board = Grid::new(width, 2 * size + 2, '.');
+    if let Some(start) = board.get_mut(size as i64, 0) {
+        *start = 'S';
+    }
+    for row in (2..board.num_rows()).step_by(2) {
+        let crowded = rng.chance(25);
+        let mut col = if crowded { 0 } else { 1 };
+        while col + usize::from(!crowded) < width {
+            if rng.chance(35) && let Some(item) = board.get_mut(col as i64, row as i64) {
+                *item = '^';
+                col += usize::from(!crowded);
+            }
+            col += 1;
+        }
+    }
+    board.to_string()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u64 = 7;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "21", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "40", params: &[] }),
+    ];
+    const VARIANTS: &'static [Variant<Day7>] = &[
+        Variant { part: 1, name: "reference", solve: part1_reference },
+        Variant { part: 2, name: "reference", solve: part2_reference },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Grid<char>;
+
+    fn parse(input: &str) -> Result<Grid<char>, AocError> {
+        Grid::parse(input)
+    }
+
+    fn part1(board: &Grid<char>, _params: &Params) -> Result<Answer, AocError> {
+        let num_splits = calc_splits_part1(board)?;
+
+        Ok(Answer::int(num_splits as u128))
+    }
+
+    fn part2(board: &Grid<char>, _params: &Params) -> Result<Answer, AocError> {
+        let start = calc_start(board).ok_or_else(
+            || AocError::invalid("No start 'S' found")
+        )?;
+        let mut known: std::collections::HashMap<Position, u128> = std::collections::HashMap::new();
+        let num_splits = calc_splits_part2(board, start, &mut known)?.checked_add(1).ok_or_else(
+            || AocError::overflow("number of timelines")
+        )?;
+        Ok(Answer::int(num_splits))
+    }
+}
@@ -1,34 +1,47 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::parse;
+use crate::params::{Param, ParamKind, Params};
+use crate::days::{Example, Solution, Variant};
+
+/// Worked example from the puzzle text. Part 1 of the example only makes 10
+/// connections instead of 1000
+pub const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
 
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Ord, PartialOrd)]
-struct Coord {
+pub struct Coord {
     x: u32,
     y: u32,
     z: u32,
 }
 
-fn parse_coords(input: &str) -> Result<Vec<Coord>, JsError> {
-    input.lines().map(|line| {
-        let mut split = line.split(",");
-
-        let mut parse = || {
-            split.next().ok_or_else(
-                || JsError::new("Unable to read")
-            ).and_then(
-                |numstr| str::parse::<u32>(numstr).or_else(
-                    |err| Err(JsError::new("unable to parse int"))
-                )
-            ).or_else(
-                |err| Err(JsError::new("Unable to parse int"))
-            )
-        };
-
-        Ok(Coord {
-            x: parse()?,
-            y: parse()?,
-            z: parse()?,
-        })
+fn parse_coords(input: &str) -> Result<Vec<Coord>, AocError> {
+    parse::lines(input).map(|line| {
+        let [x, y, z] = parse::int_array(input, line, ',', "a coordinate")?;
+        Ok(Coord { x, y, z })
     }).collect()
 }
 
@@ -69,87 +82,192 @@ fn sort_coords_into_pairs(coords: &[Coord]) -> Vec<(Coord, Coord)> {
     pairs
 }
 
+/// Every pair of boxes by index, closest first, using exact integer distances
+/// where ties go to the pair listed first
+fn pairs_reference(coords: &[Coord]) -> Vec<(usize, usize)> {
+    let squared = |a: u32, b: u32| (a.abs_diff(b) as u64).pow(2);
+    let mut pairs: Vec<(u64, usize, usize)> = Vec::new();
+    for (i, a) in coords.iter().enumerate() {
+        for (j, b) in coords.iter().enumerate().skip(i + 1) {
+            pairs.push((squared(a.x, b.x) + squared(a.y, b.y) + squared(a.z, b.z), i, j));
+        }
+    }
+    pairs.sort();
+    pairs.into_iter().map(|(_, i, j)| (i, j)).collect()
+}
 
-pub fn day8_part1(input: &str) -> Result<String, JsError> {
-    let coords = parse_coords(input)?;
-    let sorted_pairs = sort_coords_into_pairs(&coords);
+/// Union-find over box indexes, `circuit` returning the box representing a box's circuit
+struct CircuitsReference {
+    parent: Vec<usize>,
+    sizes: Vec<usize>,
+}
 
-    let mut circuits: std::collections::HashMap<Coord, usize> = coords.iter().enumerate().map(|(idx, coord)| {
-        (*coord, idx)
-    }).collect();
+impl CircuitsReference {
+    fn new(count: usize) -> CircuitsReference {
+        CircuitsReference { parent: (0..count).collect(), sizes: vec![1; count] }
+    }
 
+    fn circuit(&mut self, mut box_idx: usize) -> usize {
+        while self.parent[box_idx] != box_idx {
+            self.parent[box_idx] = self.parent[self.parent[box_idx]];
+            box_idx = self.parent[box_idx];
+        }
+        box_idx
+    }
 
-    for (coord_a, coord_b) in sorted_pairs.iter().take(1000) {
-        let circuit_a = *circuits.get(&coord_a).ok_or_else(
-            || JsError::new("Unable to find coord at index for a")
-        )?;
-        let circuit_b = *circuits.get(&coord_b).ok_or_else(
-            || JsError::new("Unable to find coord at index for b")
-        )?;
+    /// Joins the circuits of `a` and `b`, returning false if they already were one
+    fn connect(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.circuit(a), self.circuit(b));
+        if a == b {
+            return false;
+        }
+        self.parent[b] = a;
+        self.sizes[a] += self.sizes[b];
+        true
+    }
+}
 
-        for (coord, circuit) in circuits.iter_mut() {
-            if *circuit == circuit_b {
-                *circuit = circuit_a;
+fn part1_reference(coords: &[Coord], params: &Params) -> Result<Answer, AocError> {
+    let mut circuits = CircuitsReference::new(coords.len());
+    for (a, b) in pairs_reference(coords).into_iter().take(params.count("connections")?) {
+        circuits.connect(a, b);
+    }
+    let roots: Vec<usize> = (0..coords.len()).filter(|idx| circuits.circuit(*idx) == *idx).collect();
+    let mut sizes: Vec<usize> = roots.iter().map(|root| circuits.sizes[*root]).collect();
+    sizes.sort_by(|a, b| b.cmp(a));
+    Ok(Answer::int(sizes.iter().take(params.count("circuits")?).product::<usize>() as u128))
+}
+
+fn part2_reference(coords: &[Coord], _params: &Params) -> Result<Answer, AocError> {
+    let mut circuits = CircuitsReference::new(coords.len());
+    let mut remaining = coords.len();
+    for (a, b) in pairs_reference(coords) {
+        if circuits.connect(a, b) {
+            remaining -= 1;
+            if remaining == 1 {
+                return Ok(Answer::int(coords[a].x as u128 * coords[b].x as u128));
             }
         }
-        //break;
     }
+    Err(AocError::no_solution("the boxes never form a single circuit"))
+}
 
-    let circuit_counts: std::collections::HashMap<usize, usize> = circuits.into_iter().fold(
-        std::collections::HashMap::new(),
-        |mut acc, (coord, circuit)| {
-            match acc.entry(circuit) {
-                std::collections::hash_map::Entry::Occupied(mut entry) => {
-                    *entry.get_mut() += 1;
-                },
-                std::collections::hash_map::Entry::Vacant(entry) => {
-                    entry.insert(1);
-                }
-            };
-            acc
+/// `size` junction boxes at different points in a cube 100000 wide
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut seen = std::collections::HashSet::new();
+    let mut input = String::new();
+    while seen.len() < size.max(2) {
+        let [x, y, z] = [(); 3].map(|_| rng.below(100_000));
+        if seen.insert((x, y, z)) {
+            input.push_str(&format!("{x},{y},{z}\n"));
         }
-    );
+    }
+    input
+}
 
+pub struct Day8;
 
-    let mut sorted_counts: Vec<usize> = circuit_counts.values().map(|size| *size).collect();
-    sorted_counts.sort();
-    sorted_counts.reverse();
+impl Solution for Day8 {
+    const DAY: u64 = 8;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "40", params: &[("connections", "10")] }),
+        Some(Example { input: EXAMPLE, answer: "25272", params: &[] }),
+    ];
+    const PARAMS: &'static [Param] = &[
+        Param { name: "connections", kind: ParamKind::Int, default: "1000", description: "closest pairs connected in part 1" },
+        Param { name: "circuits", kind: ParamKind::Int, default: "3", description: "largest circuits multiplied together in part 1" },
+    ];
+    const VARIANTS: &'static [Variant<Day8>] = &[
+        Variant { part: 1, name: "reference", solve: |coords, params| part1_reference(coords, params) },
+        Variant { part: 2, name: "reference", solve: |coords, params| part2_reference(coords, params) },
+    ];
 
-    let mut sum: usize = sorted_counts.iter().take(3).product();
+    const GENERATOR: Option<Generator> = Some(generate);
 
-    Ok(sum.to_string())
-}
+    type Parsed = Vec<Coord>;
 
-pub fn day8_part2(input: &str) -> Result<String, JsError> {
-    let coords = parse_coords(input)?;
-    let sorted_pairs = sort_coords_into_pairs(&coords);
+    fn parse(input: &str) -> Result<Vec<Coord>, AocError> {
+        parse_coords(input)
+    }
+
+    fn part1(coords: &Vec<Coord>, params: &Params) -> Result<Answer, AocError> {
+        let sorted_pairs = sort_coords_into_pairs(coords);
 
-    let mut circuits: std::collections::HashMap<Coord, usize> = coords.iter().enumerate().map(|(idx, coord)| {
-        (*coord, idx)
-    }).collect();
+        let mut circuits: std::collections::HashMap<Coord, usize> = coords.iter().enumerate().map(|(idx, coord)| {
+            (*coord, idx)
+        }).collect();
 
 
-    for (coord_a, coord_b) in sorted_pairs {
-        let circuit_a = *circuits.get(&coord_a).ok_or_else(
-            || JsError::new("Unable to find coord at index for a")
-        )?;
-        let circuit_b = *circuits.get(&coord_b).ok_or_else(
-            || JsError::new("Unable to find coord at index for b")
-        )?;
+        for (coord_a, coord_b) in sorted_pairs.iter().take(params.count("connections")?) {
+            let circuit_a = *circuits.get(&coord_a).ok_or_else(
+                || AocError::invalid("Unable to find coord at index for a")
+            )?;
+            let circuit_b = *circuits.get(&coord_b).ok_or_else(
+                || AocError::invalid("Unable to find coord at index for b")
+            )?;
 
-        for (coord, circuit) in circuits.iter_mut() {
-            if *circuit == circuit_b {
-                *circuit = circuit_a;
+            for (coord, circuit) in circuits.iter_mut() {
+                if *circuit == circuit_b {
+                    *circuit = circuit_a;
+                }
             }
+            //break;
         }
-        //break;
-        let count_set: std::collections::HashSet<usize> = circuits.values().map(|v| *v).collect();
-        if count_set.len() == 1 {
-            return Ok((coord_a.x * coord_b.x).to_string());
-        }
+
+        let circuit_counts: std::collections::HashMap<usize, usize> = circuits.into_iter().fold(
+            std::collections::HashMap::new(),
+            |mut acc, (coord, circuit)| {
+                match acc.entry(circuit) {
+                    std::collections::hash_map::Entry::Occupied(mut entry) => {
+                        *entry.get_mut() += 1;
+                    },
+                    std::collections::hash_map::Entry::Vacant(entry) => {
+                        entry.insert(1);
+                    }
+                };
+                acc
+            }
+        );
+
+
+        let mut sorted_counts: Vec<usize> = circuit_counts.values().map(|size| *size).collect();
+        sorted_counts.sort();
+        sorted_counts.reverse();
+
+        let sum: usize = sorted_counts.iter().take(params.count("circuits")?).product();
+
+        Ok(Answer::int(sum as u128))
     }
 
+    fn part2(coords: &Vec<Coord>, _params: &Params) -> Result<Answer, AocError> {
+        let sorted_pairs = sort_coords_into_pairs(coords);
 
-    Err(JsError::new("Unable to find solution"))
+        let mut circuits: std::collections::HashMap<Coord, usize> = coords.iter().enumerate().map(|(idx, coord)| {
+            (*coord, idx)
+        }).collect();
 
+
+        for (coord_a, coord_b) in sorted_pairs {
+            let circuit_a = *circuits.get(&coord_a).ok_or_else(
+                || AocError::invalid("Unable to find coord at index for a")
+            )?;
+            let circuit_b = *circuits.get(&coord_b).ok_or_else(
+                || AocError::invalid("Unable to find coord at index for b")
+            )?;
+
+            for (coord, circuit) in circuits.iter_mut() {
+                if *circuit == circuit_b {
+                    *circuit = circuit_a;
+                }
+            }
+            //break;
+            let count_set: std::collections::HashSet<usize> = circuits.values().map(|v| *v).collect();
+            if count_set.len() == 1 {
+                return Ok(Answer::int(coord_a.x as u128 * coord_b.x as u128));
+            }
+        }
+
+
+        Err(AocError::no_solution("the boxes never form a single circuit"))
+    }
 }
@@ -1,30 +1,33 @@
-use leptos::wasm_bindgen::JsError;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::generate::{Generator, Rng};
+use crate::parse;
+use crate::params::Params;
+use crate::days::{Example, Solution, Variant};
+
+/// Worked example from the puzzle text
+pub const EXAMPLE: &str = "\
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
 
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct Coord {
+pub struct Coord {
     x: i128,
     y: i128,
 }
 
-fn parse_coords(input: &str) -> Result<Vec<Coord>, JsError> {
-    input.lines().map(|line| {
-        let mut split = line.split(",");
-
-        let mut parse = || {
-            split.next().ok_or_else(
-                || JsError::new("Unable to read next number in line")
-            ).and_then(
-                |numstr| numstr.parse::<i128>().or_else(
-                    |err| Err(JsError::new("Unable to parse int"))
-                )
-            )
-        };
-
-        Ok(Coord {
-            x: parse()?,
-            y: parse()?,
-        })
+fn parse_coords(input: &str) -> Result<Vec<Coord>, AocError> {
+    parse::lines(input).map(|line| {
+        let [x, y] = parse::int_array(input, line, ',', "a coordinate")?;
+        Ok(Coord { x, y })
     }).collect()
 }
 
@@ -49,21 +52,8 @@ fn coords_into_pairs(coords: &[Coord]) -> Vec<(Coord, Coord)> {
 }
 
 
-pub fn day9_part1(input: &str) -> Result<String, JsError> {
-    let mut coords = parse_coords(input)?;
-    let mut pairs = coords_into_pairs(&coords);
-    let max_pair = pairs.iter().max_by_key(
-        |pair| calc_area(pair)
-    ).ok_or_else(
-        || JsError::new("Unable to find max pair")
-    )?;
-    let max_area = calc_area(max_pair);
-
-    Ok(max_area.to_string())
-}
-
-fn calc_vert_walls(coords: &Vec<Coord>) -> Result<std::collections::BTreeMap<i128, Vec<VertWall>>, JsError> {
-    let mut prev: &Coord = coords.last().ok_or_else(|| JsError::new(&"Could not find last coord"))?;
+fn calc_vert_walls(coords: &Vec<Coord>) -> Result<std::collections::BTreeMap<i128, Vec<VertWall>>, AocError> {
+    let mut prev: &Coord = coords.last().ok_or_else(|| AocError::invalid("Could not find last coord"))?;
 
     let mut walls: std::collections::BTreeMap<i128, Vec<VertWall>> = std::collections::BTreeMap::new();
     for coord in coords {
@@ -98,8 +88,8 @@ struct VertWall {
     y2: i128,
 }
 
-fn calc_horz_walls(coords: &Vec<Coord>) -> Result<std::collections::BTreeMap<i128, Vec<HorzWall>>, JsError> {
-    let mut prev: &Coord = coords.last().ok_or_else(|| JsError::new(&"Could not find last coord"))?;
+fn calc_horz_walls(coords: &Vec<Coord>) -> Result<std::collections::BTreeMap<i128, Vec<HorzWall>>, AocError> {
+    let mut prev: &Coord = coords.last().ok_or_else(|| AocError::invalid("Could not find last coord"))?;
 
     let mut walls: std::collections::BTreeMap<i128, Vec<HorzWall>> = std::collections::BTreeMap::new();
     for coord in coords {
@@ -126,96 +116,239 @@ fn overlap(a_min: i128, a_max: i128, b_min: i128, b_max: i128) -> bool {
     a_max >= b_min && a_min <= b_max
 }
 
-pub fn day9_part2(input: &str) -> Result<String, JsError> {
-    let coords = parse_coords(input)?;
-    let pairs = coords_into_pairs(&coords);
-
-    //
-    let global_min_x = coords.iter().map(|coord| coord.x).min().ok_or_else(|| JsError::new("Unable to find min x"))?;
+/// Indexes for each distinct value along one axis of a compressed floor, with a
+/// cell for the gap after a value when the next one isn't adjacent and an empty
+/// border all round. Returns the index of each value and the number of cells
+fn compress_reference(values: impl Iterator<Item = i128>) -> (std::collections::HashMap<i128, usize>, usize) {
+    let values: std::collections::BTreeSet<i128> = values.collect();
+    let mut indexes = std::collections::HashMap::new();
+    let mut next = 1;
+    let mut values = values.into_iter().peekable();
+    while let Some(value) = values.next() {
+        indexes.insert(value, next);
+        next += 1;
+        if values.peek().is_some_and(|following| following - value > 1) {
+            next += 1;
+        }
+    }
+    (indexes, next + 1)
+}
 
-    let vert_walls = calc_vert_walls(&coords)?;
-    let horz_walls = calc_horz_walls(&coords)?;
+/// Marks the loop on a compressed floor, floods in from the border to find the
+/// outside, then checks each rectangle cell by cell (through prefix sums of
+/// outside cells) instead of casting rays
+fn part2_reference(coords: &[Coord], _params: &Params) -> Result<Answer, AocError> {
+    let (xs, width) = compress_reference(coords.iter().map(|coord| coord.x));
+    let (ys, height) = compress_reference(coords.iter().map(|coord| coord.y));
+    let cell = |coord: &Coord| (xs[&coord.x], ys[&coord.y]);
+
+    let mut wall = vec![vec![false; width]; height];
+    for (a, b) in coords.iter().zip(coords.iter().cycle().skip(1)) {
+        let ((ax, ay), (bx, by)) = (cell(a), cell(b));
+        if ax != bx && ay != by {
+            return Err(AocError::invalid("consecutive tiles must share a row or column"));
+        }
+        for row in wall.iter_mut().take(ay.max(by) + 1).skip(ay.min(by)) {
+            for item in row.iter_mut().take(ax.max(bx) + 1).skip(ax.min(bx)) {
+                *item = true;
+            }
+        }
+    }
 
-    let is_vert_wall_span = |x, y1, y2| {
-        match vert_walls.get(&x) {
-            Some(_vec) => {
-                _vec.iter().any(|wall| overlap(y1, y2, wall.y1, wall.y2) || overlap(y1, y2, wall.y1, wall.y2))
-            },
-            None => false
+    let mut outside = vec![vec![false; width]; height];
+    let mut stack = vec![(0, 0)];
+    while let Some((x, y)) = stack.pop() {
+        if outside[y][x] || wall[y][x] {
+            continue;
         }
-    };
+        outside[y][x] = true;
+        // The border is always outside, so stepping off the floor only happens from
+        // a border cell and wrapping_sub lands far out of range
+        let neighbours = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+        stack.extend(neighbours.into_iter().filter(|&(x, y)| x < width && y < height));
+    }
 
-    let overlap_horz_wall = |min_x: i128, min_y: i128, max_x: i128, max_y: i128| {
-        if min_x > max_x || min_y > max_y {
-            return false;
+    // outside_before[y][x] counts outside cells above and left of (x, y)
+    let mut outside_before = vec![vec![0usize; width + 1]; height + 1];
+    for y in 0..height {
+        for x in 0..width {
+            outside_before[y + 1][x + 1] = outside_before[y][x + 1] + outside_before[y + 1][x] - outside_before[y][x]
+                + outside[y][x] as usize;
         }
+    }
 
-        for (k, vs) in horz_walls.range(min_y..=max_y) {
-            for wall in vs {
-                if overlap(min_x, max_x, wall.x1, wall.x2) {
-                    return true;
-                }
+    let mut max_area = None;
+    for (idx, a) in coords.iter().enumerate() {
+        for b in coords.iter().skip(idx + 1) {
+            let ((ax, ay), (bx, by)) = (cell(a), cell(b));
+            let (x1, x2, y1, y2) = (ax.min(bx), ax.max(bx) + 1, ay.min(by), ay.max(by) + 1);
+            let outside_cells = outside_before[y2][x2] + outside_before[y1][x1] - outside_before[y1][x2] - outside_before[y2][x1];
+            if outside_cells == 0 {
+                max_area = max_area.max(Some(calc_area(&(*a, *b))));
             }
+        }
+    }
+    let max_area = max_area.ok_or_else(|| AocError::no_solution("Unable to find max pair in bounds"))?;
+    Ok(Answer::int(u128::try_from(max_area).map_err(|_| AocError::invalid("negative area"))?))
+}
 
+/// A loop of about `size` red tiles, going right along the tops of a row of bars
+/// and back along their bottoms. Neighbouring bars differ in height so every
+/// tile is a corner
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let bars = size.div_ceil(4).max(1);
+    let mut xs = vec![rng.between(1, 100)];
+    for _ in 0..bars {
+        xs.push(xs[xs.len() - 1] + rng.between(2, 1000));
+    }
+    let mut heights = |low: u64, high: u64| {
+        let mut heights: Vec<u64> = Vec::with_capacity(bars);
+        while heights.len() < bars {
+            let height = rng.between(low, high);
+            if heights.last() != Some(&height) {
+                heights.push(height);
+            }
         }
-        false
+        heights
+    };
+    let tops = heights(50_001, 100_000);
+    let bottoms = heights(1, 49_999);
 
+    let mut tiles = Vec::with_capacity(4 * bars);
+    for bar in 0..bars {
+        tiles.push((xs[bar], tops[bar]));
+        tiles.push((xs[bar + 1], tops[bar]));
+    }
+    for bar in (0..bars).rev() {
+        tiles.push((xs[bar + 1], bottoms[bar]));
+        tiles.push((xs[bar], bottoms[bar]));
+    }
+    tiles.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+}
 
-    };
-    let overlap_vert_wall = |min_x: i128, min_y: i128, max_x: i128, max_y: i128| {
-        if min_x > max_x || min_y > max_y {
-            return false;
-        }
+pub struct Day9;
 
-        for (k, vs) in vert_walls.range(min_x..=max_x) {
-            for wall in vs {
-                if overlap(min_y, max_y, wall.y1, wall.y2) {
-                    return true;
-                }
+impl Solution for Day9 {
+    const DAY: u64 = 9;
+    const EXAMPLES: [Option<Example>; 2] = [
+        Some(Example { input: EXAMPLE, answer: "50", params: &[] }),
+        Some(Example { input: EXAMPLE, answer: "24", params: &[] }),
+    ];
+    const VARIANTS: &'static [Variant<Day9>] = &[
+        Variant { part: 2, name: "reference", solve: |coords, params| part2_reference(coords, params) },
+    ];
+
+    const GENERATOR: Option<Generator> = Some(generate);
+
+    type Parsed = Vec<Coord>;
+
+    fn parse(input: &str) -> Result<Vec<Coord>, AocError> {
+        parse_coords(input)
+    }
+
+    fn part1(coords: &Vec<Coord>, _params: &Params) -> Result<Answer, AocError> {
+        let pairs = coords_into_pairs(coords);
+        let max_pair = pairs.iter().max_by_key(
+            |pair| calc_area(pair)
+        ).ok_or_else(
+            || AocError::no_solution("Unable to find max pair")
+        )?;
+        let max_area = calc_area(max_pair);
+
+        Ok(Answer::int(u128::try_from(max_area).map_err(|_| AocError::invalid("negative area"))?))
+    }
+
+    fn part2(coords: &Vec<Coord>, _params: &Params) -> Result<Answer, AocError> {
+        let pairs = coords_into_pairs(coords);
+
+        //
+        let global_min_x = coords.iter().map(|coord| coord.x).min().ok_or_else(|| AocError::invalid("Unable to find min x"))?;
+
+        let vert_walls = calc_vert_walls(coords)?;
+        let horz_walls = calc_horz_walls(coords)?;
+
+        // the ray runs between rows y1 and y2, so a wall only crosses it if it covers
+        // both. One that just ends on either row turns back without crossing
+        let is_vert_wall_span = |x, y1, y2| {
+            match vert_walls.get(&x) {
+                Some(_vec) => {
+                    _vec.iter().any(|wall: &VertWall| wall.y1 <= y1 && y2 <= wall.y2)
+                },
+                None => false
             }
+        };
 
-        }
-        false
-    };
+        let overlap_horz_wall = |min_x: i128, min_y: i128, max_x: i128, max_y: i128| {
+            if min_x > max_x || min_y > max_y {
+                return false;
+            }
 
-    let areas: Vec<(Coord, Coord)> = pairs.iter().filter_map(
-        |pair| {
-            let min_x = pair.0.x.min(pair.1.x);
-            let max_x = pair.0.x.max(pair.1.x);
-            let min_y = pair.0.y.min(pair.1.y);
-            let max_y = pair.0.y.max(pair.1.y);
-
-            let target = Coord { x: min_x + 1, y: min_y + 1 };
-
-            let mut outside = true;
-            for x in global_min_x..target.x {
-                // iterate from left until meeting target
-                // y will be assumed to be top, x will be to left of pixel
-                let is_wall = is_vert_wall_span(x, target.y - 1, target.y);
-                if is_wall {
-                    outside = !outside;
+            for (k, vs) in horz_walls.range(min_y..=max_y) {
+                for wall in vs {
+                    if overlap(min_x, max_x, wall.x1, wall.x2) {
+                        return true;
+                    }
                 }
-            }
 
-            if outside {
-                return None;
             }
+            false
 
 
-            if overlap_horz_wall(min_x + 1, min_y + 1 , max_x - 1, max_y - 1 ) {
-                return None;
+        };
+        let overlap_vert_wall = |min_x: i128, min_y: i128, max_x: i128, max_y: i128| {
+            if min_x > max_x || min_y > max_y {
+                return false;
             }
-            if overlap_vert_wall(min_x + 1, min_y + 1 , max_x - 1, max_y - 1) {
-                return None;
+
+            for (k, vs) in vert_walls.range(min_x..=max_x) {
+                for wall in vs {
+                    if overlap(min_y, max_y, wall.y1, wall.y2) {
+                        return true;
+                    }
+                }
+
             }
+            false
+        };
 
-            Some(Ok((Coord { x: min_x, y: min_y }, Coord {x: max_x, y: max_y })))
-        }
-    ).collect::<Result<Vec<(Coord, Coord)>, JsError>>()?;
-    let max_area = areas.iter().map(
-        |pair| calc_area(pair)
-    ).max().ok_or_else(|| JsError::new("Unable to find max pair in bounds"))?;
+        let areas: Vec<(Coord, Coord)> = pairs.iter().filter_map(
+            |pair| {
+                let min_x = pair.0.x.min(pair.1.x);
+                let max_x = pair.0.x.max(pair.1.x);
+                let min_y = pair.0.y.min(pair.1.y);
+                let max_y = pair.0.y.max(pair.1.y);
+
+                let target = Coord { x: min_x + 1, y: min_y + 1 };
+
+                let mut outside = true;
+                for x in global_min_x..target.x {
+                    // iterate from left until meeting target
+                    // y will be assumed to be top, x will be to left of pixel
+                    let is_wall = is_vert_wall_span(x, target.y - 1, target.y);
+                    if is_wall {
+                        outside = !outside;
+                    }
+                }
+
+                if outside {
+                    return None;
+                }
 
-    Ok(max_area.to_string())
-}
 
+                if overlap_horz_wall(min_x + 1, min_y + 1 , max_x - 1, max_y - 1 ) {
+                    return None;
+                }
+                if overlap_vert_wall(min_x + 1, min_y + 1 , max_x - 1, max_y - 1) {
+                    return None;
+                }
+
+                Some(Ok((Coord { x: min_x, y: min_y }, Coord {x: max_x, y: max_y })))
+            }
+        ).collect::<Result<Vec<(Coord, Coord)>, AocError>>()?;
+        let max_area = areas.iter().map(
+            |pair| calc_area(pair)
+        ).max().ok_or_else(|| AocError::no_solution("Unable to find max pair in bounds"))?;
+
+        Ok(Answer::int(u128::try_from(max_area).map_err(|_| AocError::invalid("negative area"))?))
+    }
+}
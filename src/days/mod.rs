@@ -1,52 +1,256 @@
-use leptos::{view, IntoView};
-use leptos::leptos_dom::error;
-use leptos::prelude::{signal, Action};
-use leptos::wasm_bindgen::JsError;
-use leptos::prelude::*;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
+use std::panic::{self, AssertUnwindSafe};
 
+use crate::answer::Answer;
+use crate::bench::BenchJob;
+use crate::error::AocError;
+use crate::generate::Generator;
+use crate::params::{Param, ParamOverrides, Params};
+use crate::timing::{now_ms, Timings};
+
+/// A worked example from the puzzle text and the answer the puzzle gives for it
+pub struct Example {
+    pub input: &'static str,
+    pub answer: &'static str,
+    /// Parameters the example uses instead of the real puzzle's, e.g. day 8's
+    /// example makes 10 connections rather than 1000
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+impl Example {
+    pub fn overrides(&self) -> ParamOverrides {
+        self.params.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+}
+
+/// One day's puzzle. The input is parsed once and both parts solve from the
+/// parsed form, so parse errors are reported the same way for either part
+pub trait Solution: Sized + 'static {
+    const DAY: u64;
+    /// Examples for part 1 and part 2, usually the same one
+    const EXAMPLES: [Option<Example>; 2];
+    /// Other ways of solving a part, next to `part1` and `part2` which are the
+    /// `DEFAULT_VARIANT`
+    const VARIANTS: &'static [Variant<Self>] = &[];
+    /// Values either part reads from `Params` instead of hard-coding
+    const PARAMS: &'static [Param] = &[];
+    /// Makes random inputs of this day's shape, for stress-testing the solvers
+    const GENERATOR: Option<Generator> = None;
+
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError>;
+    fn part1(parsed: &Self::Parsed, params: &Params) -> Result<Answer, AocError>;
+    fn part2(parsed: &Self::Parsed, params: &Params) -> Result<Answer, AocError>;
+}
+
+pub const PARTS: [u64; 2] = [1, 2];
+
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Solves one part from a day's parsed input. A variant written to take a slice
+/// of a `Vec` input fits through a closure, e.g. `|ranges, params| part1_reference(ranges, params)`
+pub type Solver<S> = fn(&<S as Solution>::Parsed, &Params) -> Result<Answer, AocError>;
+
+/// A named alternative solver for one part, e.g. a brute force kept around to
+/// check an optimized default against
+pub struct Variant<S: Solution> {
+    pub part: u64,
+    pub name: &'static str,
+    pub solve: Solver<S>,
+}
+
+/// A part and the name of one of its variants
+pub type VariantChoice<'a> = (u64, &'a str);
+
+/// One part's answer and how long it took
+pub type PartRun = (Result<Answer, AocError>, Timings);
+
+/// `solve_variants` for one `Solution`
+type SolveFn = fn(&str, &[VariantChoice], &[(String, String)]) -> Vec<PartRun>;
+
+/// A registered `Solution` with its types erased, so every day fits in one registry
+pub struct Day {
+    pub day: u64,
+    pub examples: [Option<Example>; 2],
+    pub params: &'static [Param],
+    pub generator: Option<Generator>,
+    solve: SolveFn,
+    variants: fn(u64) -> Vec<&'static str>,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Day {
+        Day {
+            day: S::DAY,
+            examples: S::EXAMPLES,
+            params: S::PARAMS,
+            generator: S::GENERATOR,
+            solve: solve_variants::<S>,
+            variants: variant_names::<S>,
+        }
+    }
+
+    /// Names of every variant of `part`, `DEFAULT_VARIANT` first
+    pub fn variants(&self, part: u64) -> Vec<&'static str> {
+        (self.variants)(part)
+    }
+
+    pub fn example(&self, part: u64) -> Option<&Example> {
+        self.examples.get(part.checked_sub(1)? as usize)?.as_ref()
+    }
+
+    /// Parses the input once and runs each (part, variant) in `runs` on it with
+    /// the given parameter overrides. Every run is charged the full parse time
+    pub fn run_variants(&self, input: &str, runs: &[VariantChoice], params: &[(String, String)]) -> Vec<PartRun> {
+        (self.solve)(input, runs, params)
+    }
+
+    pub fn run_parts(&self, input: &str, parts: &[u64], params: &[(String, String)]) -> Vec<PartRun> {
+        let runs: Vec<VariantChoice> = parts.iter().map(|part| (*part, DEFAULT_VARIANT)).collect();
+        self.run_variants(input, &runs, params)
+    }
+
+    pub fn run_variant(&self, input: &str, part: u64, variant: &str, params: &[(String, String)]) -> PartRun {
+        self.run_variants(input, &[(part, variant)], params).remove(0)
+    }
+
+    pub fn run(&self, input: &str, part: u64, params: &[(String, String)]) -> PartRun {
+        self.run_variant(input, part, DEFAULT_VARIANT, params)
+    }
+}
+
+fn variant_names<S: Solution>(part: u64) -> Vec<&'static str> {
+    let default = PARTS.contains(&part).then_some(DEFAULT_VARIANT);
+    default.into_iter().chain(
+        S::VARIANTS.iter().filter(|variant| variant.part == part).map(|variant| variant.name)
+    ).collect()
+}
+
+fn find_solver<S: Solution>(part: u64, variant: &str) -> Option<Solver<S>> {
+    match (part, variant) {
+        (1, DEFAULT_VARIANT) => Some(S::part1),
+        (2, DEFAULT_VARIANT) => Some(S::part2),
+        _ => S::VARIANTS.iter().find(|v| v.part == part && v.name == variant).map(|v| v.solve),
+    }
+}
+
+fn solve_variants<S: Solution>(input: &str, runs: &[VariantChoice], overrides: &[(String, String)]) -> Vec<PartRun> {
+    let start = now_ms();
+    // bad parameters fail every run the same way a parse error does
+    let parsed = Params::resolve(S::PARAMS, overrides).and_then(|params| Ok((S::parse(input)?, params)));
+    let parse_ms = now_ms() - start;
+
+    runs.iter().map(|(part, variant)| {
+        let (parsed, params) = match &parsed {
+            Ok((parsed, params)) => (parsed, params),
+            Err(err) => return (Err(err.clone()), Timings { parse_ms, solve_ms: 0.0 }),
+        };
+        let start = now_ms();
+        let result = match find_solver::<S>(*part, variant) {
+            Some(solve) => solve(parsed, params),
+            None => Err(AocError::no_solution(
+                format!("No variant {variant:?} found for day {} part {part}", S::DAY)
+            )),
+        };
+        (result, Timings { parse_ms, solve_ms: now_ms() - start })
+    }).collect()
+}
+
+/// Runs `solve`, turning a panic into that run failing with `AocError::Panicked`,
+/// so one broken solver doesn't take a whole batch of them down with it. Where
+/// panics abort, as in the browser, this catches nothing
+pub fn catching_panics(solve: impl FnOnce() -> PartRun) -> PartRun {
+    panic::catch_unwind(AssertUnwindSafe(solve)).unwrap_or_else(
+        |payload| (Err(AocError::panicked(&*payload)), Timings { parse_ms: 0.0, solve_ms: 0.0 })
+    )
+}
+
+/// Runs `variant` of every job, one result per job in order. Neighbouring jobs on
+/// the same day, input and parameters, like both parts of a day, share one parse.
+/// A group that panics is run again a part at a time, so only the part that
+/// panicked fails
+pub fn run_jobs(jobs: &[BenchJob], variant: &str) -> Vec<PartRun> {
+    jobs.chunk_by(|a, b| a.day == b.day && a.params == b.params && a.input == b.input).flat_map(|group| {
+        let (day, input, params) = (group[0].day, &group[0].input, &group[0].params);
+        let runs: Vec<VariantChoice> = group.iter().map(|job| (job.part, variant)).collect();
+        let Some(solution) = find_day(day) else {
+            return group.iter().map(|job| (
+                Err(AocError::no_solution(format!("No function found for day {day} part {}", job.part))),
+                Timings { parse_ms: 0.0, solve_ms: 0.0 },
+            )).collect();
+        };
+        panic::catch_unwind(AssertUnwindSafe(|| solution.run_variants(input, &runs, params))).unwrap_or_else(|_| {
+            runs.iter().map(|&(part, variant)| catching_panics(|| solution.run_variant(input, part, variant, params))).collect()
+        })
+    }).collect()
+}
+
+/// One part of a registered day
+#[derive(Clone, Copy)]
 pub struct DayPart {
     pub day: u64,
     pub part: u64,
-    pub func: fn(&str) -> Result<String, JsError>,
-}
-
-pub const DAY_PARTS: [DayPart; 24] = [
-    DayPart { day: 1, part: 1, func: day1::day1_part1 },
-    DayPart { day: 1, part: 2, func: day1::day1_part2 },
-    DayPart { day: 2, part: 1, func: day2::day2_part1 },
-    DayPart { day: 2, part: 2, func: day2::day2_part2 },
-    DayPart { day: 3, part: 1, func: day3::day3_part1 },
-    DayPart { day: 3, part: 2, func: day3::day3_part2 },
-    DayPart { day: 4, part: 1, func: day4::day4_part1 },
-    DayPart { day: 4, part: 2, func: day4::day4_part2 },
-    DayPart { day: 5, part: 1, func: day5::day5_part1 },
-    DayPart { day: 5, part: 2, func: day5::day5_part2 },
-    DayPart { day: 6, part: 1, func: day6::day6_part1 },
-    DayPart { day: 6, part: 2, func: day6::day6_part2 },
-    DayPart { day: 7, part: 1, func: day7::day7_part1 },
-    DayPart { day: 7, part: 2, func: day7::day7_part2 },
-    DayPart { day: 8, part: 1, func: day8::day8_part1 },
-    DayPart { day: 8, part: 2, func: day8::day8_part2 },
-    DayPart { day: 9, part: 1, func: day9::day9_part1 },
-    DayPart { day: 9, part: 2, func: day9::day9_part2 },
-    DayPart { day: 10, part: 1, func: day10::day10_part1 },
-    DayPart { day: 10, part: 2, func: day10::day10_part2 },
-    DayPart { day: 11, part: 1, func: day11::day11_part1 },
-    DayPart { day: 11, part: 2, func: day11::day11_part2 },
-    DayPart { day: 12, part: 1, func: day12::day12_part1 },
-    DayPart { day: 12, part: 2, func: day12::day12_part2 },
-];
+    pub example: Option<&'static Example>,
+    solution: &'static Day,
+}
+
+impl DayPart {
+    pub fn run(&self, input: &str, params: &[(String, String)]) -> PartRun {
+        self.solution.run(input, self.part, params)
+    }
 
+    pub fn variants(&self) -> Vec<&'static str> {
+        self.solution.variants(self.part)
+    }
+
+    pub fn run_variant(&self, input: &str, variant: &str, params: &[(String, String)]) -> PartRun {
+        self.solution.run_variant(input, self.part, variant, params)
+    }
+
+    pub fn params(&self) -> &'static [Param] {
+        self.solution.params
+    }
+}
+
+/// Declares each day's module and registers its `Solution` in `DAYS`. Adding a
+/// day only takes a new line here
+macro_rules! register_days {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        pub static DAYS: &[Day] = &[$(Day::of::<$module::$solution>()),*];
+    };
+}
+
+register_days! {
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+}
+
+pub fn find_day(day: u64) -> Option<&'static Day> {
+    DAYS.iter().find(|registered| registered.day == day)
+}
+
+/// Every part of every registered day, in order
+pub fn day_parts() -> impl Iterator<Item = DayPart> {
+    DAYS.iter().flat_map(|solution| PARTS.map(|part| DayPart {
+        day: solution.day,
+        part,
+        example: solution.example(part),
+        solution,
+    }))
+}
+
+pub fn find_daypart(day: u64, part: u64) -> Option<DayPart> {
+    day_parts().find(|dp| dp.day == day && dp.part == part)
+}
@@ -0,0 +1,197 @@
+use crate::error::AocError;
+use crate::parse;
+
+/// The dial that instructions without a prefix turn
+pub const DEFAULT_DIAL: &str = "dial";
+
+/// A ring of positions numbered from 0, like day 1's safe dial, that keeps count of
+/// how many turns end on 0 and how many times it passes 0 (ending on it included)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dial {
+    pub name: String,
+    pub positions: i64,
+    pub position: i64,
+    pub ends_on_zero: u64,
+    pub passes_zero: u64,
+}
+
+impl Dial {
+    pub fn new(name: impl Into<String>, positions: i64, start: i64) -> Result<Dial, AocError> {
+        let name = name.into();
+        if positions <= 0 {
+            return Err(AocError::invalid(format!("dial {name} needs at least one position, got {positions}")));
+        }
+        Ok(Dial { name, positions, position: start.rem_euclid(positions), ends_on_zero: 0, passes_zero: 0 })
+    }
+
+    /// How many times turning `clicks` (negative for left) would pass 0, counting
+    /// where it stops. Measured from the last 0 behind the dial (its mirror image
+    /// when turning left), a turn passes one 0 for every full turn it reaches
+    pub fn zeros_passed(&self, clicks: i64) -> u64 {
+        let from_zero = if clicks < 0 { (self.positions - self.position) % self.positions } else { self.position };
+        ((from_zero + clicks.abs()) / self.positions) as u64
+    }
+
+    /// Turns the dial and updates both counts, returning how many times it passed 0
+    pub fn turn(&mut self, clicks: i64) -> u64 {
+        let passed = self.zeros_passed(clicks);
+        self.position = (self.position + clicks).rem_euclid(self.positions);
+        self.passes_zero += passed;
+        if self.position == 0 {
+            self.ends_on_zero += 1;
+        }
+        passed
+    }
+}
+
+/// One instruction: which dial to turn, by index into `Instructions::dials`, and by
+/// how many clicks, negative for left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    pub dial: usize,
+    pub clicks: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instructions {
+    /// Dial names in the order they're first turned
+    pub dials: Vec<String>,
+    /// One per line, so turn n is on line n
+    pub turns: Vec<Turn>,
+}
+
+/// Reads one instruction per line: an L or R followed by a number of clicks, e.g.
+/// "L68", optionally prefixed by the dial to turn, e.g. "b:R14". Blank lines are
+/// only allowed at the end
+pub fn parse_instructions(input: &str) -> Result<Instructions, AocError> {
+    let mut instructions = Instructions { dials: Vec::new(), turns: Vec::new() };
+    for line in input.trim_end().lines() {
+        let (name, rotation) = match line.split_once(':') {
+            Some((name, rotation)) => {
+                if name.is_empty() || !name.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+                    return Err(AocError::parse_at(input, name, format!("expected a dial name before ':', found {name:?}")));
+                }
+                (name, rotation)
+            }
+            None => (DEFAULT_DIAL, line),
+        };
+        let (sign, clicks) = match rotation.split_at_checked(1) {
+            Some(("L", clicks)) => (-1, clicks),
+            Some(("R", clicks)) => (1, clicks),
+            _ => return Err(AocError::parse_at(
+                input, rotation, format!("expected a rotation like L68 or R14, found {rotation:?}")
+            )),
+        };
+        let clicks: u32 = parse::int(input, clicks, "a number of clicks")?;
+
+        let dial = match instructions.dials.iter().position(|other| other == name) {
+            Some(dial) => dial,
+            None => {
+                instructions.dials.push(name.to_string());
+                instructions.dials.len() - 1
+            }
+        };
+        instructions.turns.push(Turn { dial, clicks: sign * i64::from(clicks) });
+    }
+    Ok(instructions)
+}
+
+/// A dial for each name, with `positions` positions and starting at `start` unless
+/// `rings` gives it its own, written like "a=60@10,b=12@0" for dial a having 60
+/// positions and starting at 10
+pub fn setup(names: &[String], positions: i64, start: i64, rings: &str) -> Result<Vec<Dial>, AocError> {
+    let mut dials = names.iter().map(|name| Dial::new(name.as_str(), positions, start)).collect::<Result<Vec<_>, _>>()?;
+    for ring in parse::list(rings, ',').filter(|ring| !ring.is_empty()) {
+        let expected = || AocError::invalid(format!("expected a dial's ring like a=60@10, found {ring:?}"));
+        let (name, size) = ring.split_once('=').ok_or_else(expected)?;
+        let (positions, start) = size.split_once('@').ok_or_else(expected)?;
+        let (positions, start) = (
+            positions.trim().parse().map_err(|_| expected())?,
+            start.trim().parse().map_err(|_| expected())?,
+        );
+        let name = name.trim();
+        let dial = dials.iter_mut().find(|dial| dial.name == name).ok_or_else(
+            || AocError::invalid(format!("no instruction turns dial {name}, which has a ring set"))
+        )?;
+        *dial = Dial::new(name, positions, start)?;
+    }
+    Ok(dials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (positions, start, clicks, zeros passed, where it stops)
+    const TURNS: &[(i64, i64, i64, u64, i64)] = &[
+        // starting on 0
+        (100, 0, 1, 0, 1),
+        (100, 0, -1, 0, 99),
+        (100, 0, 0, 0, 0),
+        (100, 0, 100, 1, 0),
+        (100, 0, -100, 1, 0),
+        (100, 0, 99, 0, 99),
+        (100, 0, -99, 0, 1),
+        // landing on 0
+        (100, 50, 50, 1, 0),
+        (100, 50, -50, 1, 0),
+        (100, 1, -1, 1, 0),
+        (100, 99, 1, 1, 0),
+        // several full turns
+        (100, 50, 250, 3, 0),
+        (100, 50, -250, 3, 0),
+        (100, 50, 1000, 10, 50),
+        (100, 50, -1000, 10, 50),
+        (100, 0, 300, 3, 0),
+        (100, 0, -301, 3, 99),
+        (100, 20, 379, 3, 99),
+        (100, 20, -379, 4, 41),
+        // a custom ring
+        (12, 0, 12, 1, 0),
+        (12, 0, -12, 1, 0),
+        (12, 0, -11, 0, 1),
+        (12, 5, 7, 1, 0),
+        (12, 5, -5, 1, 0),
+        (12, 5, -17, 2, 0),
+        (12, 5, 31, 3, 0),
+        (12, 5, -30, 3, 11),
+        (1, 0, 5, 5, 0),
+        (1, 0, -5, 5, 0),
+    ];
+
+    /// The old way, one click at a time
+    fn stepping(positions: i64, start: i64, clicks: i64) -> (u64, i64) {
+        let (mut position, mut passed) = (start, 0);
+        for _ in 0..clicks.abs() {
+            position = (position + clicks.signum()).rem_euclid(positions);
+            if position == 0 {
+                passed += 1;
+            }
+        }
+        (passed, position)
+    }
+
+    #[test]
+    fn turns_pass_zero_like_stepping() {
+        for &(positions, start, clicks, passes, stops) in TURNS {
+            let mut dial = Dial::new("dial", positions, start).unwrap();
+            let case = format!("{clicks} clicks from {start} on a ring of {positions}");
+            assert_eq!(stepping(positions, start, clicks), (passes, stops), "stepping {case}");
+            assert_eq!(dial.zeros_passed(clicks), passes, "{case}");
+            assert_eq!(dial.turn(clicks), passes, "{case}");
+            assert_eq!(dial.position, stops, "{case}");
+            assert_eq!(dial.passes_zero, passes, "{case}");
+            assert_eq!(dial.ends_on_zero, u64::from(stops == 0), "{case}");
+        }
+    }
+
+    #[test]
+    fn rings_from_setup_turn_on_their_own_size() {
+        let instructions = parse_instructions("R50\nb:L17\nb:R31\nL250\n").unwrap();
+        let mut dials = setup(&instructions.dials, 100, 50, "b=12@5").unwrap();
+        let passed: Vec<u64> = instructions.turns.iter().map(|turn| dials[turn.dial].turn(turn.clicks)).collect();
+        assert_eq!(passed, [1, 2, 2, 2]);
+        assert_eq!((dials[0].position, dials[0].ends_on_zero, dials[0].passes_zero), (50, 1, 3));
+        assert_eq!((dials[1].position, dials[1].ends_on_zero, dials[1].passes_zero), (7, 1, 4));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::crosscheck::{self, VariantRun};
+use crate::error::AocError;
+use crate::generate;
+
+/// A generated input the variants of a part don't agree on, shrunk as far as it
+/// would go while they still disagree
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub day: u64,
+    pub part: u64,
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub runs: Vec<VariantRun>,
+}
+
+/// The runs on `input` if any variant panics, or if at least one answers and
+/// another disagrees with the default. Inputs the variants can't agree to reject
+/// aren't interesting
+fn disagreement(day: u64, part: u64, input: &str) -> Result<Option<Vec<VariantRun>>, AocError> {
+    let runs = crosscheck::cross_check(day, part, input, &[])?;
+    let answered = runs.iter().any(|run| run.result.is_ok());
+    let panicked = shape(&runs).contains(&Outcome::Panicked);
+    Ok((panicked || answered && !crosscheck::disagreements(&runs).is_empty()).then_some(runs))
+}
+
+/// Generates inputs for every seed in `seeds` at each size from 1 to `max_size`,
+/// smallest first, and returns the first one the variants disagree on, shrunk
+pub fn find_mismatch(
+    day: u64,
+    part: u64,
+    seeds: impl IntoIterator<Item = u64> + Clone,
+    max_size: usize,
+) -> Result<Option<Mismatch>, AocError> {
+    for size in 1..=max_size {
+        for seed in seeds.clone() {
+            let input = generate::generate(day, seed, size)?;
+            if let Some(runs) = disagreement(day, part, &input)? {
+                let (input, runs) = shrink(day, part, input, runs);
+                return Ok(Some(Mismatch { day, part, seed, size, input, runs }));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Smaller versions of `input`, each missing one line or one comma-separated item
+fn candidates(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let rejoin = |lines: Vec<String>| lines.iter().map(|line| format!("{line}\n")).collect::<String>();
+
+    let mut candidates = Vec::new();
+    for skip in 0..lines.len() {
+        let fewer = lines.iter().enumerate().filter(|(idx, _)| *idx != skip).map(|(_, line)| line.to_string());
+        candidates.push(rejoin(fewer.collect()));
+    }
+    for (idx, line) in lines.iter().enumerate() {
+        let items: Vec<&str> = line.split(',').collect();
+        for skip in 0..items.len().saturating_sub(1) {
+            let mut shorter: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+            shorter[idx] = items.iter().enumerate().filter(|(item, _)| *item != skip).map(|(_, item)| *item).collect::<Vec<_>>().join(",");
+            candidates.push(rejoin(shorter));
+        }
+    }
+    candidates
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Answered,
+    Errored,
+    Panicked,
+}
+
+/// How each variant's run ended
+fn shape(runs: &[VariantRun]) -> Vec<Outcome> {
+    runs.iter().map(|run| match &run.result {
+        Ok(_) => Outcome::Answered,
+        Err(AocError::Panicked(_)) => Outcome::Panicked,
+        Err(_) => Outcome::Errored,
+    }).collect()
+}
+
+/// Drops lines and list items one at a time for as long as the variants still
+/// disagree in the same way, with the same variants answering, erroring and
+/// panicking. Anything else, like a candidate that panics a different solver or
+/// that the solvers reject for a different reason, is a different failure to the
+/// one being shrunk
+fn shrink(day: u64, part: u64, mut input: String, mut runs: Vec<VariantRun>) -> (String, Vec<VariantRun>) {
+    let shape_of_failure = shape(&runs);
+    'shrinking: loop {
+        for candidate in candidates(&input) {
+            if let Ok(Some(candidate_runs)) = disagreement(day, part, &candidate) && shape(&candidate_runs) == shape_of_failure {
+                (input, runs) = (candidate, candidate_runs);
+                continue 'shrinking;
+            }
+        }
+        return (input, runs);
+    }
+}
+
+pub fn format_mismatch(mismatch: &Mismatch) -> String {
+    format!(
+        "Day {} part {} variants disagree (generated with seed {}, size {}), shrunk to:\n{}\n{}",
+        mismatch.day,
+        mismatch.part,
+        mismatch.seed,
+        mismatch.size,
+        mismatch.input,
+        crosscheck::format_cross_check(&mismatch.runs),
+    )
+}
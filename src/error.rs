@@ -0,0 +1,116 @@
+use std::any::Any;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AocError {
+    /// The input could not be parsed. `line` and `column` are 1-based, 0 means unknown
+    Parse { line: usize, column: usize, message: String },
+    /// An intermediate value didn't fit in its integer type
+    Overflow(String),
+    /// A node the puzzle refers to is not in the input
+    MissingNode(String),
+    /// The input parsed but doesn't have the shape the puzzle describes
+    InvalidStructure(String),
+    /// The solver finished without finding an answer
+    NoSolution(String),
+    /// The solver panicked, caught by a runner that carries on with other solvers
+    Panicked(String),
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> AocError {
+        AocError::Parse { line, column, message: message.into() }
+    }
+
+    /// Parse error for `piece`, which must be a slice of `input`, located by its offset within `input`
+    pub fn parse_at(input: &str, piece: &str, message: impl Into<String>) -> AocError {
+        let (line, column) = location(input, piece);
+        AocError::parse(line, column, message)
+    }
+
+    pub fn overflow(message: impl Into<String>) -> AocError {
+        AocError::Overflow(message.into())
+    }
+
+    pub fn missing_node(name: impl Into<String>) -> AocError {
+        AocError::MissingNode(name.into())
+    }
+
+    pub fn invalid(message: impl Into<String>) -> AocError {
+        AocError::InvalidStructure(message.into())
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> AocError {
+        AocError::NoSolution(message.into())
+    }
+
+    /// The message of a panic caught with `catch_unwind`, when it has one
+    pub fn panicked(payload: &(dyn Any + Send)) -> AocError {
+        let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "no message".to_string());
+        AocError::Panicked(message)
+    }
+
+    /// The 1-based input line this error points at, if it points at one
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            AocError::Parse { line, .. } if *line > 0 => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// The error followed by the input line it points at, if any, with a caret
+    /// under the column
+    pub fn with_context(&self, input: &str) -> String {
+        let mut text = self.to_string();
+        let Some(line) = self.line() else {
+            return text;
+        };
+        if let Some(line_text) = input.lines().nth(line - 1) {
+            let prefix = format!("{line} | ");
+            text.push_str(&format!("\n\n{prefix}{line_text}"));
+            if let AocError::Parse { column, .. } = self && *column > 0 {
+                text.push_str(&format!("\n{}^", " ".repeat(prefix.len() + column - 1)));
+            }
+        }
+        text
+    }
+}
+
+/// 1-based (line, column) of `piece` within `input`, or (0, 0) if `piece` isn't a slice of `input`
+pub fn location(input: &str, piece: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let offset = (piece.as_ptr() as usize).wrapping_sub(start);
+    if offset > input.len() {
+        return (0, 0);
+    }
+
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    let column = input[line_start..offset].chars().count() + 1;
+    (line, column)
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse { line: 0, message, .. } => write!(f, "parse error: {message}"),
+            AocError::Parse { line, column: 0, message } => write!(f, "parse error on line {line}: {message}"),
+            AocError::Parse { line, column, message } => {
+                write!(f, "parse error on line {line}, column {column}: {message}")
+            }
+            AocError::Overflow(message) => write!(f, "overflow: {message}"),
+            AocError::MissingNode(name) => write!(f, "missing node: {name}"),
+            AocError::InvalidStructure(message) => write!(f, "invalid input: {message}"),
+            AocError::NoSolution(message) => write!(f, "no solution: {message}"),
+            AocError::Panicked(message) => write!(f, "solver panicked: {message}"),
+        }
+    }
+}
+
+/// Also gives `From<AocError> for JsError` through wasm-bindgen's blanket impl
+impl std::error::Error for AocError {}
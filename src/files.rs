@@ -0,0 +1,34 @@
+use leptos::wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::{Array, ArrayBuffer, Uint8Array};
+use web_sys::{Blob, BlobPropertyBag, File, HtmlAnchorElement, Url};
+
+/// Reads a picked or dropped file exactly as it is on disk. The bytes are decoded
+/// without any newline or whitespace normalization, which column-aligned inputs
+/// like day 6's depend on
+pub async fn read_file(file: File) -> Result<String, String> {
+    let buffer = JsFuture::from(file.array_buffer()).await.map_err(
+        |err| format!("Could not read {}: {:?}", file.name(), err)
+    )?;
+    let bytes = Uint8Array::new(&ArrayBuffer::from(buffer)).to_vec();
+    String::from_utf8(bytes).map_err(|err| format!("{} is not valid UTF-8: {}", file.name(), err))
+}
+
+/// Saves `contents` through the browser's download prompt
+pub fn download(file_name: &str, mime_type: &str, contents: &str) -> Result<(), JsValue> {
+    let parts = Array::new();
+    parts.push(&contents.into());
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window().and_then(|window| window.document()).ok_or_else(
+        || JsValue::from_str("no document to download from")
+    )?;
+    let anchor: HtmlAnchorElement = document.create_element("a")?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    Url::revoke_object_url(&url)
+}
@@ -0,0 +1,68 @@
+use crate::days::find_day;
+use crate::error::AocError;
+
+/// Makes a random puzzle-shaped input of roughly `size` items (lines, ranges,
+/// devices, etc. depending on the day) that the day's solvers can answer
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// The size the page and the CLI start from
+pub const DEFAULT_SIZE: usize = 50;
+
+/// A small seeded generator (splitmix64), so the same seed gives the same input on
+/// every platform, wasm included, without pulling in an entropy source
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, which must not be empty
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "can't pick a number below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// A number in `low..=high`
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        match (high - low).checked_add(1) {
+            Some(span) => low + self.below(span),
+            None => self.next_u64(),
+        }
+    }
+
+    /// True `percent` times out of 100
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx as u64 + 1) as usize);
+        }
+    }
+}
+
+/// A random input for `day`, the same every time for the same seed and size
+pub fn generate(day: u64, seed: u64, size: usize) -> Result<String, AocError> {
+    let solution = find_day(day).ok_or_else(|| AocError::no_solution(format!("No solution found for day {day}")))?;
+    let generator = solution.generator.ok_or_else(
+        || AocError::no_solution(format!("Day {day} has no input generator"))
+    )?;
+    Ok(generator(&mut Rng::new(seed), size))
+}
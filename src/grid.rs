@@ -0,0 +1,233 @@
+use std::fmt;
+
+use crate::error::AocError;
+
+/// Offsets to the four orthogonal neighbours, as (col, row)
+const ORTHOGONAL: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to all eight neighbours, diagonals included, as (col, row)
+const SURROUNDING: [(i64, i64); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// A rectangular board stored row by row. Positions are (col, row) with (0, 0) at the
+/// top left, and are i64 so a neighbour off the edge is just out of bounds rather
+/// than an underflow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    items: Vec<T>,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(num_cols: usize, num_rows: usize, fill: T) -> Grid<T> {
+        Grid { items: vec![fill; num_cols * num_rows], num_rows, num_cols }
+    }
+
+    /// Rows become columns, so (col, row) moves to (row, col)
+    pub fn transpose(&self) -> Grid<T> {
+        let items = (0..self.num_cols).flat_map(|col| self.column(col as i64)).cloned().collect();
+        Grid { items, num_rows: self.num_cols, num_cols: self.num_rows }
+    }
+
+    /// A quarter turn clockwise, so the first column becomes the first row, reversed
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let items = (0..self.num_cols).flat_map(|col| {
+            (0..self.num_rows).rev().map(move |row| self.items[row * self.num_cols + col].clone())
+        }).collect();
+        Grid { items, num_rows: self.num_cols, num_cols: self.num_rows }
+    }
+
+    /// A quarter turn counterclockwise, so the last column becomes the first row
+    pub fn rotate_counterclockwise(&self) -> Grid<T> {
+        let items = (0..self.num_cols).rev().flat_map(|col| self.column(col as i64)).cloned().collect();
+        Grid { items, num_rows: self.num_cols, num_cols: self.num_rows }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Reads one item per character, ignoring blank lines at the end. Every line
+    /// must be as wide as the first
+    pub fn parse_with(
+        input: &str,
+        mut item: impl FnMut(char, i64, i64) -> Result<T, AocError>,
+    ) -> Result<Grid<T>, AocError> {
+        let lines: Vec<&str> = input.trim_end().lines().collect();
+        let num_cols = lines.first().map(|line| line.chars().count()).unwrap_or(0);
+        if num_cols == 0 {
+            return Err(AocError::parse(1, 0, "First line is empty"));
+        }
+
+        let mut items = Vec::with_capacity(num_cols * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let width = line.chars().count();
+            if width != num_cols {
+                return Err(AocError::parse(row + 1, 0, format!("found {width} columns, expected {num_cols}")));
+            }
+            for (col, ch) in line.chars().enumerate() {
+                items.push(item(ch, col as i64, row as i64)?);
+            }
+        }
+
+        Ok(Grid { items, num_rows: lines.len(), num_cols })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    fn index(&self, col: i64, row: i64) -> Option<usize> {
+        let in_bounds = (0..self.num_cols as i64).contains(&col) && (0..self.num_rows as i64).contains(&row);
+        in_bounds.then(|| row as usize * self.num_cols + col as usize)
+    }
+
+    pub fn contains(&self, col: i64, row: i64) -> bool {
+        self.index(col, row).is_some()
+    }
+
+    pub fn get(&self, col: i64, row: i64) -> Option<&T> {
+        self.index(col, row).map(|idx| &self.items[idx])
+    }
+
+    pub fn get_mut(&mut self, col: i64, row: i64) -> Option<&mut T> {
+        self.index(col, row).map(|idx| &mut self.items[idx])
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (i64, i64)> + use<T> {
+        let num_cols = self.num_cols as i64;
+        (0..self.num_rows as i64).flat_map(move |row| (0..num_cols).map(move |col| (col, row)))
+    }
+
+    /// Positions and items, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.positions().zip(self.items.iter())
+    }
+
+    /// The in-bounds positions above, right of, below and left of (col, row)
+    pub fn neighbours4(&self, col: i64, row: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.offsets(col, row, &ORTHOGONAL)
+    }
+
+    /// The in-bounds positions around (col, row), diagonals included
+    pub fn neighbours8(&self, col: i64, row: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.offsets(col, row, &SURROUNDING)
+    }
+
+    fn offsets(&self, col: i64, row: i64, offsets: &'static [(i64, i64)]) -> impl Iterator<Item = (i64, i64)> + '_ {
+        offsets.iter()
+            .map(move |(d_col, d_row)| (col + d_col, row + d_row))
+            .filter(|(col, row)| self.contains(*col, *row))
+    }
+
+    pub fn row(&self, row: i64) -> Option<&[T]> {
+        let start = self.index(0, row)?;
+        Some(&self.items[start..start + self.num_cols])
+    }
+
+    /// Items in one column, top to bottom. Empty if the column is out of bounds
+    pub fn column(&self, col: i64) -> impl Iterator<Item = &T> {
+        let in_bounds = (0..self.num_cols as i64).contains(&col);
+        let items = if in_bounds { &self.items[col as usize..] } else { &[] };
+        items.iter().step_by(self.num_cols.max(1))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.items.chunks(self.num_cols.max(1))
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The first position holding `value`, row by row
+    pub fn find(&self, value: &T) -> Option<(i64, i64)> {
+        self.iter().find(|(_, item)| *item == value).map(|(position, _)| position)
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (i64, i64)> + 'a {
+        self.iter().filter(move |(_, item)| *item == value).map(|(position, _)| position)
+    }
+}
+
+impl Grid<char> {
+    /// A board of characters, as most puzzle inputs are
+    pub fn parse(input: &str) -> Result<Grid<char>, AocError> {
+        Grid::parse_with(input, |ch, _, _| Ok(ch))
+    }
+}
+
+/// One line per row, with items side by side, which for `Grid<char>` prints the
+/// board as it appeared in the input
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for item in row {
+                write!(f, "{item}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Grid<char> {
+        Grid::parse("abc\ndef\n").unwrap()
+    }
+
+    #[test]
+    fn transposes() {
+        assert_eq!(board().transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(board().transpose().transpose(), board());
+    }
+
+    #[test]
+    fn rotates() {
+        assert_eq!(board().rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(board().rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(board().rotate_clockwise().rotate_counterclockwise(), board());
+        let turned = (0..4).fold(board(), |grid, _| grid.rotate_clockwise());
+        assert_eq!(turned, board());
+    }
+
+    #[test]
+    fn columns_out_of_bounds_are_empty() {
+        let grid = board();
+        assert_eq!(grid.column(0).collect::<String>(), "ad");
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.column(-1).count(), 0);
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn rows_out_of_bounds_are_none() {
+        let grid = board();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(-1), None);
+        assert_eq!(grid.row(2), None);
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_board() {
+        let grid = board();
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4(2, 1).collect::<Vec<_>>(), [(2, 0), (1, 1)]);
+        assert_eq!(grid.neighbours4(1, 0).collect::<Vec<_>>(), [(2, 0), (1, 1), (0, 0)]);
+        assert_eq!(grid.neighbours4(-1, 0).collect::<Vec<_>>(), [(0, 0)]);
+        assert_eq!(grid.neighbours8(0, 0).count(), 3);
+        assert_eq!(grid.neighbours8(1, 1).count(), 5);
+    }
+
+    #[test]
+    fn finds_every_match() {
+        let grid = Grid::parse("a.a\n.a.\n").unwrap();
+        assert_eq!(grid.find(&'a'), Some((0, 0)));
+        assert_eq!(grid.find_all(&'a').collect::<Vec<_>>(), [(0, 0), (2, 0), (1, 1)]);
+        assert_eq!(grid.find(&'z'), None);
+    }
+}
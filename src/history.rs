@@ -0,0 +1,140 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::days::DEFAULT_VARIANT;
+use crate::error::AocError;
+use crate::params::ParamOverrides;
+use crate::timing::Timings;
+
+/// How many runs are kept, the oldest are dropped first. The history keeps their
+/// inputs so they can be re-run, which keeps this small enough for local storage
+pub const HISTORY_LIMIT: usize = 30;
+
+/// One solve, with everything needed to run it again apart from the input, which
+/// the history keeps by `input_hash`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,
+    pub day: u64,
+    pub part: u64,
+    pub variant: String,
+    pub params: ParamOverrides,
+    pub input_hash: String,
+    pub result: Result<Answer, AocError>,
+    pub timings: Timings,
+    /// When the run finished, as shown to the user
+    pub timestamp: String,
+}
+
+impl HistoryEntry {
+    /// e.g. "day 10 part 1 (subsets) with connections=10", leaving out the defaults
+    pub fn label(&self) -> String {
+        let mut label = format!("day {} part {}", self.day, self.part);
+        if self.variant != DEFAULT_VARIANT {
+            label.push_str(&format!(" ({})", self.variant));
+        }
+        if !self.params.is_empty() {
+            let params: Vec<String> = self.params.iter().map(|(name, value)| format!("{name}={value}")).collect();
+            label.push_str(&format!(" with {}", params.join(", ")));
+        }
+        label
+    }
+
+    pub fn outcome(&self) -> String {
+        match &self.result {
+            Ok(answer) => answer.to_string(),
+            Err(err) => format!("error: {err}"),
+        }
+    }
+}
+
+/// Previous runs, newest first, and the inputs they ran on. Runs on the same input
+/// share one copy of it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    entries: VecDeque<HistoryEntry>,
+    #[serde(default)]
+    inputs: HashMap<String, String>,
+    next_id: u64,
+}
+
+impl History {
+    pub fn from_json(json: &str) -> Result<History, String> {
+        serde_json::from_str(json).map_err(|err| format!("Could not read history: {err}"))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("history always serializes")
+    }
+
+    /// Adds a run on `input`, giving it the next id. Inputs no run uses any more
+    /// are dropped along with the oldest runs
+    pub fn record(&mut self, mut entry: HistoryEntry, input: String) {
+        entry.id = self.next_id;
+        self.next_id += 1;
+        self.inputs.insert(entry.input_hash.clone(), input);
+        self.entries.push_front(entry);
+        self.entries.truncate(HISTORY_LIMIT);
+        let entries = &self.entries;
+        self.inputs.retain(|hash, _| entries.iter().any(|entry| entry.input_hash == *hash));
+    }
+
+    /// The input a run used, if the history still has it. Histories saved before
+    /// inputs were shared don't
+    pub fn input(&self, entry: &HistoryEntry) -> Option<&str> {
+        self.inputs.get(&entry.input_hash).map(String::as_str)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    pub fn find(&self, id: u64) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.inputs.clear();
+    }
+}
+
+/// What changed from `before` to `after`: the inputs they ran on, the answer, each
+/// diagnostic and the time taken
+pub fn diff(before: &HistoryEntry, after: &HistoryEntry) -> String {
+    let mut lines = vec![format!(
+        "{} at {} -> {} at {}", before.label(), before.timestamp, after.label(), after.timestamp
+    )];
+    if before.input_hash != after.input_hash {
+        lines.push(format!("input: {} -> {}", before.input_hash, after.input_hash));
+    }
+    let (before_outcome, after_outcome) = (before.outcome(), after.outcome());
+    if before_outcome == after_outcome {
+        lines.push(format!("answer: {after_outcome} (unchanged)"));
+    } else {
+        lines.push(format!("answer: {before_outcome} -> {after_outcome}"));
+    }
+
+    let diagnostics = |entry: &HistoryEntry| entry.result.as_ref().map(|answer| answer.diagnostics.clone()).unwrap_or_default();
+    let (before_diagnostics, after_diagnostics) = (diagnostics(before), diagnostics(after));
+    let lookup = |diagnostics: &[(String, String)], name: &str| {
+        diagnostics.iter().find(|(other, _)| other == name).map(|(_, value)| value.as_str()).unwrap_or("-").to_string()
+    };
+    let mut names: Vec<&str> = Vec::new();
+    for (name, _) in before_diagnostics.iter().chain(after_diagnostics.iter()) {
+        if !names.contains(&name.as_str()) {
+            names.push(name);
+        }
+    }
+    for name in names {
+        let (old, new) = (lookup(&before_diagnostics, name), lookup(&after_diagnostics, name));
+        if old != new {
+            lines.push(format!("{name}: {old} -> {new}"));
+        }
+    }
+
+    lines.push(format!("time: {:.3} ms -> {:.3} ms", before.timings.total_ms(), after.timings.total_ms()));
+    lines.join("\n")
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+
+/// 64-bit FNV-1a of the input, as hex. Inputs are identified by this rather than
+/// stored, so a shared ledger doesn't carry anyone's puzzle input
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// An answer accepted for one part on one input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub day: u64,
+    pub part: u64,
+    pub input_hash: String,
+    pub answer: String,
+}
+
+impl LedgerEntry {
+    fn is_for(&self, day: u64, part: u64, input_hash: &str) -> bool {
+        self.day == day && self.part == part && self.input_hash == input_hash
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerStatus {
+    /// Nothing has been accepted for this input yet
+    Unrecorded,
+    Matches,
+    Regressed { accepted: String },
+}
+
+/// Accepted answers, shared as a JSON file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    pub fn from_json(json: &str) -> Result<Ledger, String> {
+        serde_json::from_str(json).map_err(|err| format!("Could not read ledger: {err}"))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("ledgers always serialize")
+    }
+
+    fn find(&self, day: u64, part: u64, input_hash: &str) -> Option<&LedgerEntry> {
+        self.entries.iter().find(|entry| entry.is_for(day, part, input_hash))
+    }
+
+    /// Records `answer` for the input, replacing whatever was accepted before
+    pub fn accept(&mut self, day: u64, part: u64, input_hash: &str, answer: &Answer) {
+        self.entries.retain(|entry| !entry.is_for(day, part, input_hash));
+        self.entries.push(LedgerEntry { day, part, input_hash: input_hash.to_string(), answer: answer.to_string() });
+    }
+
+    pub fn check(&self, day: u64, part: u64, input_hash: &str, answer: &Answer) -> LedgerStatus {
+        match self.find(day, part, input_hash) {
+            None => LedgerStatus::Unrecorded,
+            Some(entry) if entry.answer == answer.to_string() => LedgerStatus::Matches,
+            Some(entry) => LedgerStatus::Regressed { accepted: entry.answer.clone() },
+        }
+    }
+
+    /// Adds another ledger's entries, which win where both have accepted an answer
+    pub fn merge(&mut self, other: Ledger) {
+        for entry in other.entries {
+            self.entries.retain(|existing| !existing.is_for(entry.day, entry.part, &entry.input_hash));
+            self.entries.push(entry);
+        }
+    }
+}
+
+impl LedgerStatus {
+    pub fn describe(&self) -> String {
+        match self {
+            LedgerStatus::Unrecorded => "no accepted answer for this input".to_string(),
+            LedgerStatus::Matches => "matches the accepted answer".to_string(),
+            LedgerStatus::Regressed { accepted } => format!("REGRESSION, the accepted answer is {accepted}"),
+        }
+    }
+}
@@ -3,41 +3,413 @@ extern crate core;
 
 use leptos::{view, IntoView};
 use leptos::leptos_dom::error;
-use leptos::prelude::{signal, Action};
-use leptos::wasm_bindgen::JsError;
+use leptos::prelude::signal;
 use leptos::prelude::*;
 
+pub mod answer;
+pub mod bench;
+pub mod client;
+pub mod crosscheck;
+pub mod dashboard;
 pub mod days;
+pub mod dial;
+pub mod difftest;
+pub mod error;
+pub mod files;
+pub mod generate;
+pub mod grid;
+pub mod history;
+pub mod ledger;
+pub mod params;
+pub mod parse;
+pub mod protocol;
+pub mod storage;
+pub mod timing;
+pub mod verify;
+
+use crate::answer::{Answer, Trace};
+use crate::bench::BenchJob;
+use crate::client::SolverWorker;
+use crate::dashboard::{DashboardRow, RowStatus};
+use crate::error::AocError;
+use crate::history::HistoryEntry;
+use crate::ledger::Ledger;
+use crate::params::{ParamKind, ParamOverrides};
+use crate::protocol::{Request, Response};
+use crate::timing::Timings;
+
+fn render_result(result: Result<Answer, AocError>, timings: Timings, input: &str) -> String {
+    match result {
+        Ok(_result) => {
+            let mut message = _result.to_string();
+            for (name, value) in _result.diagnostics.iter() {
+                message.push_str(&format!("\n{name}: {value}"));
+            }
+            message.push_str(&format!("\n\nparse: {:.3} ms, solve: {:.3} ms", timings.parse_ms, timings.solve_ms));
+            message
+        },
+        Err(_err) => format!("Error: {}", _err.with_context(input)),
+    }
+}
+
+/// UTF-16 offsets of the start and end of a 1-based line, which is what textarea
+/// selections are measured in
+fn line_span_utf16(text: &str, line: usize) -> Option<(u32, u32)> {
+    let mut start = 0;
+    for (idx, line_text) in text.split_inclusive('\n').enumerate() {
+        let content = line_text.trim_end_matches(['\r', '\n']);
+        if idx + 1 == line {
+            return Some((start, start + content.encode_utf16().count() as u32));
+        }
+        start += line_text.encode_utf16().count() as u32;
+    }
+    None
+}
 
 pub fn Main() -> impl IntoView {
-    let (input_text, set_input_text) = signal("".to_string());
+    // each day keeps its own input in local storage, the textarea shows the selected day's
+    let initial_day = storage::load_selected_day().unwrap_or(1);
+    let (selected_day, set_selected_day) = signal(initial_day);
+    let (input_text, set_input_text) = signal(storage::load_input(initial_day).unwrap_or_default());
     let (message, set_message) = signal("".to_string());
+    // why the last save to local storage failed, if it did, kept apart from the message so a result doesn't hide it
+    let (save_error, set_save_error) = signal(None::<String>);
+    let report_save = move |saved: Result<(), String>| set_save_error.set(saved.err());
+    // the worker drops requests sent before it has loaded, so wait for its Ready message
+    let (ready, set_ready) = signal(false);
+    let (pending, set_pending) = signal(false);
+    let (bench_runs, set_bench_runs) = signal(10usize);
+    let (ledger, set_ledger) = signal(storage::load_ledger());
+    // the last successful solve, as (day, part, input hash, answer), which can be accepted into the ledger
+    let (last_solved, set_last_solved) = signal(None::<(u64, u64, String, Answer)>);
+    // None when the solve used changed parameters, whose answers don't belong in the ledger
+    let solving_hash: StoredValue<Option<String>> = StoredValue::new(None);
+    // rows from the last run of everything, each with its input hash, None when parameters were changed
+    let (dashboard, set_dashboard) = signal(Vec::<(DashboardRow, Option<String>)>::new());
+    let dashboard_hashes: StoredValue<std::collections::HashMap<u64, Option<String>>> = StoredValue::new(Default::default());
+    // the steps of the last solve, when it was a trace variant
+    let (trace, set_trace) = signal(None::<Trace>);
+    let (history, set_history) = signal(storage::load_history());
+    // the variant, parameters and input of the solve in flight, to record it in the history
+    let solving: StoredValue<Option<(String, ParamOverrides, String)>> = StoredValue::new(None);
+    // ids of up to two history entries to diff
+    let (compared, set_compared) = signal(Vec::<u64>::new());
+    // parameters the user changed from their defaults, by day
+    let (param_overrides, set_param_overrides) = signal(std::collections::HashMap::<u64, ParamOverrides>::new());
+    let overrides_for = move |day: u64| param_overrides.with_untracked(|overrides| overrides.get(&day).cloned().unwrap_or_default());
+    let set_param = move |day: u64, name: &'static str, default: &'static str, value: String| {
+        set_param_overrides.update(|overrides| {
+            let day_overrides = overrides.entry(day).or_default();
+            day_overrides.retain(|(other, _)| other != name);
+            if value != default {
+                day_overrides.push((name.to_string(), value));
+            }
+        });
+    };
 
-    let process = Action::new(move |input: &(u64, u64)| {
-        let (day, part) = *input;
-        async move {
-            let daypart = crate::days::DAY_PARTS.iter().find(
-                |dp| dp.day == day && dp.part == part
-            ).ok_or_else(||
-                JsError::new(format!("No function found for day {} part {}", day, part).as_str())
-            );
-            let result = daypart.and_then(|_daypart| {
-                (_daypart.func)(input_text.get().as_ref())
-            });
-            match result {
-                Ok(_result) => {
-                    set_message.set(_result);
-                },
-                Err(_err) => {
-                    set_message.set("Error, see console".to_string());
-                    error!("Error: {:?}", _err);
+    // selects the line an error points at so it shows up in the textarea
+    let textarea: NodeRef<leptos::html::Textarea> = NodeRef::new();
+    let show_error_line = move |err: &AocError| {
+        let (Some(line), Some(textarea)) = (err.line(), textarea.get_untracked()) else {
+            return;
+        };
+        if let Some((start, end)) = line_span_utf16(&textarea.value(), line) {
+            let _ = textarea.focus();
+            let _ = textarea.set_selection_range(start, end);
+        }
+    };
+
+    let on_response = move |response: Response| {
+        match response {
+            Response::Ready => set_ready.set(true),
+            Response::Solved { day, part, result, timings } => {
+                if let (Ok(answer), Some(input_hash)) = (&result, solving_hash.get_value()) {
+                    set_last_solved.set(Some((day, part, input_hash, answer.clone())));
                 }
-            };
+                let (variant, params, input) = solving.get_value().unwrap_or_else(
+                    || (days::DEFAULT_VARIANT.to_string(), Vec::new(), input_text.get_untracked())
+                );
+                solving.set_value(None);
+                // a re-run from the history may not be on the input in the textarea
+                if let (Err(err), true) = (&result, input == input_text.get_untracked()) {
+                    show_error_line(err);
+                }
+                set_message.set(render_result(result.clone(), timings, &input));
+                set_trace.set(result.as_ref().ok().and_then(|answer| answer.trace.clone()));
+                // traces can run to thousands of rows, too many to keep in local storage
+                let result = result.map(|answer| Answer { trace: None, ..answer });
+                let timestamp = web_sys::js_sys::Date::new_0().to_locale_string("default", &leptos::wasm_bindgen::JsValue::UNDEFINED).into();
+                set_history.update(|history| {
+                    let input_hash = ledger::input_hash(&input);
+                    history.record(HistoryEntry { id: 0, day, part, variant, params, input_hash, result, timings, timestamp }, input);
+                    report_save(storage::save_history(history));
+                });
+                set_pending.set(false);
+            }
+            Response::CrossChecked { day, part, result } => {
+                match result {
+                    Ok(runs) => set_message.set(
+                        format!("day {day} part {part} cross-check:\n{}", crosscheck::format_cross_check(&runs))
+                    ),
+                    Err(err) => {
+                        show_error_line(&err);
+                        set_message.set(format!("Error: {}", err.with_context(&input_text.get_untracked())));
+                    }
+                }
+                set_pending.set(false);
+            }
+            Response::Benched { reports } => {
+                set_message.set(bench::format_reports(&reports));
+                set_pending.set(false);
+            }
+            Response::RanAll { rows } => {
+                let rows = dashboard_hashes.with_value(|hashes| {
+                    rows.into_iter().map(|row| {
+                        let input_hash = hashes.get(&row.day).cloned().flatten();
+                        (row, input_hash)
+                    }).collect()
+                });
+                set_dashboard.set(rows);
+                set_pending.set(false);
+            }
+            Response::Verified { verifications } => {
+                set_message.set(verify::format_verifications(&verifications));
+                set_pending.set(false);
+            }
+        }
+    };
+    // bumping the generation replaces the worker, which terminates whatever the old one was running
+    let (generation, set_generation) = signal(0u64);
+    let on_error = move |err: String| {
+        error!("Worker error: {}", err);
+        set_pending.set(false);
+        // a worker that never got as far as Ready failed to load, and a fresh one
+        // would only fail the same way, over and over
+        if !ready.get_untracked() {
+            set_message.set(format!("The solver worker failed to load: {err}\nReload the page to try again, details are in the console"));
+            return;
         }
+        set_message.set(format!("The solver stopped unexpectedly: {err}\nIt has been restarted, the full panic message is in the console"));
+        // a panic leaves the worker's wasm unusable, so start a fresh one
+        set_generation.update(|generation| *generation += 1);
+    };
+    let worker: StoredValue<Option<SolverWorker>, LocalStorage> = StoredValue::new_local(None);
+    Effect::new(move |_| {
+        generation.track();
+        set_ready.set(false);
+        worker.set_value(SolverWorker::spawn(on_response, on_error).map_err(|err| {
+            error!("Could not start worker: {:?}", err);
+            set_message.set("Could not start the solver worker, see console".to_string());
+        }).ok());
     });
 
+    let cancel = move |_| {
+        set_generation.update(|generation| *generation += 1);
+        set_pending.set(false);
+        set_message.set("cancelled".to_string());
+    };
+
+    let send = move |request: Request| {
+        worker.with_value(|worker| {
+            if let Some(worker) = worker {
+                match worker.send(&request) {
+                    Ok(()) => set_pending.set(true),
+                    Err(err) => error!("Could not send to worker: {:?}", err),
+                }
+            }
+        });
+    };
+    let select_day = move |day: u64| {
+        if day != selected_day.get_untracked() {
+            set_selected_day.set(day);
+            set_input_text.set(storage::load_input(day).unwrap_or_default());
+            report_save(storage::save_selected_day(day));
+        }
+    };
+    let set_input = move |text: String| {
+        report_save(storage::save_input(selected_day.get_untracked(), &text));
+        set_input_text.set(text);
+    };
+
+    let solve = move |day: u64, part: u64, variant: String, params: ParamOverrides, input: String| {
+        solving_hash.set_value(params.is_empty().then(|| ledger::input_hash(&input)));
+        solving.set_value(Some((variant.clone(), params.clone(), input.clone())));
+        set_last_solved.set(None);
+        send(Request::Solve { day, part, variant, params, input });
+    };
+    let process = move |day: u64, part: u64, variant: String| {
+        select_day(day);
+        solve(day, part, variant, overrides_for(day), input_text.get_untracked());
+    };
+    // runs a history entry again with its own input and parameters, whatever the textarea holds
+    let rerun = move |id: u64| {
+        let found = history.with_untracked(|history| {
+            history.find(id).map(|entry| (entry.clone(), history.input(entry).map(str::to_string)))
+        });
+        match found {
+            Some((entry, Some(input))) => solve(entry.day, entry.part, entry.variant, entry.params, input),
+            Some((entry, None)) => set_message.set(format!("The input for {} is no longer stored, so it can't be run again", entry.label())),
+            None => {}
+        }
+    };
+    let toggle_compared = move |id: u64| {
+        set_compared.update(|compared| {
+            if let Some(idx) = compared.iter().position(|other| *other == id) {
+                compared.remove(idx);
+            } else {
+                compared.push(id);
+                if compared.len() > 2 {
+                    compared.remove(0);
+                }
+            }
+        });
+    };
+    let history_diff = move || {
+        let mut ids = compared.get();
+        ids.sort();
+        let [before, after] = ids[..] else {
+            return "Tick two runs to compare their answers".to_string();
+        };
+        history.with(|history| match (history.find(before), history.find(after)) {
+            (Some(before), Some(after)) => history::diff(before, after),
+            _ => String::new(),
+        })
+    };
+    let clear_history = move |_| {
+        set_compared.set(Vec::new());
+        set_history.update(|history| {
+            history.clear();
+            report_save(storage::save_history(history));
+        });
+    };
+
+    let accept = move |_| {
+        if let Some((day, part, input_hash, answer)) = last_solved.get_untracked() {
+            set_ledger.update(|ledger| {
+                ledger.accept(day, part, &input_hash, &answer);
+                report_save(storage::save_ledger(ledger));
+            });
+        }
+    };
+    let ledger_status = move || {
+        last_solved.with(|solved| solved.as_ref().map(|(day, part, input_hash, answer)| {
+            format!("Ledger: {}", ledger.with(|ledger| ledger.check(*day, *part, input_hash, answer)).describe())
+        }))
+    };
+    let export_ledger = move |_| {
+        if let Err(err) = files::download("ledger.json", "application/json", &ledger.with_untracked(Ledger::to_json)) {
+            error!("Could not export ledger: {:?}", err);
+        }
+    };
+    let import_ledger = move |file: web_sys::File| {
+        leptos::task::spawn_local(async move {
+            match files::read_file(file).await.and_then(|json| Ledger::from_json(&json)) {
+                Ok(imported) => set_ledger.update(|ledger| {
+                    ledger.merge(imported);
+                    report_save(storage::save_ledger(ledger));
+                }),
+                Err(err) => set_message.set(err),
+            }
+        });
+    };
+    let cross_check = move |day: u64, part: u64| {
+        select_day(day);
+        send(Request::CrossCheck { day, part, params: overrides_for(day), input: input_text.get_untracked() });
+    };
+    let benchmark = move |day: u64| {
+        select_day(day);
+        let input = input_text.get_untracked();
+        let jobs = [1, 2].map(|part| BenchJob { day, part, params: overrides_for(day), input: input.clone() }).to_vec();
+        send(Request::Bench { jobs, runs: bench_runs.get_untracked() });
+    };
+
+    let run_all = move |_| {
+        let mut jobs = Vec::new();
+        let mut hashes = std::collections::HashMap::new();
+        let mut missing = Vec::new();
+        for solution in days::DAYS {
+            let day = solution.day;
+            // the selected day's input may not have been saved yet, so take it from the textarea
+            let input = if day == selected_day.get_untracked() {
+                Some(input_text.get_untracked())
+            } else {
+                storage::load_input(day)
+            };
+            let Some(input) = input.filter(|input| !input.trim().is_empty()) else {
+                missing.push(day.to_string());
+                continue;
+            };
+            let params = overrides_for(day);
+            hashes.insert(day, params.is_empty().then(|| ledger::input_hash(&input)));
+            jobs.extend(days::PARTS.map(|part| BenchJob { day, part, params: params.clone(), input: input.clone() }));
+        }
+        dashboard_hashes.set_value(hashes);
+        set_message.set(if missing.is_empty() {
+            String::new()
+        } else {
+            format!("No stored input for day {}", missing.join(", "))
+        });
+        send(Request::RunAll { jobs });
+    };
+    let dashboard_rows = move || {
+        dashboard.with(|rows| rows.iter().map(|(row, input_hash)| {
+            let checked = match (&row.result, input_hash) {
+                (Ok(answer), Some(input_hash)) => Some(ledger.with(|ledger| ledger.check(row.day, row.part, input_hash, answer))),
+                _ => None,
+            };
+            let answer = row.result.as_ref().map(ToString::to_string).unwrap_or_default();
+            let status = RowStatus::of(row, checked);
+            let color = match status {
+                RowStatus::Mismatch { .. } | RowStatus::Error(_) => "color: #d33;",
+                _ => "",
+            };
+            view! {
+                <tr>
+                    <td>{row.day}</td>
+                    <td>{row.part}</td>
+                    <td>{answer}</td>
+                    <td>{format!("{:.3} ms", row.timings.total_ms())}</td>
+                    <td style={color}>{status.to_string()}</td>
+                </tr>
+            }
+        }).collect::<Vec<_>>())
+    };
+
+    let trace_table = move || trace.with(|trace| trace.as_ref().map(|trace| view! {
+        <table style="text-align: left;">
+            <thead>
+                <tr>{trace.columns.iter().map(|column| view! { <th>{column.clone()}</th> }).collect::<Vec<_>>()}</tr>
+            </thead>
+            <tbody>
+                {trace.rows.iter().map(|row| view! {
+                    <tr>{row.iter().map(|cell| view! { <td>{cell.clone()}</td> }).collect::<Vec<_>>()}</tr>
+                }).collect::<Vec<_>>()}
+            </tbody>
+        </table>
+    }));
+
+    // seed and size for "Generate input", which fills the textarea with a random input for the selected day
+    let (generate_seed, set_generate_seed) = signal(1u64);
+    let (generate_size, set_generate_size) = signal(generate::DEFAULT_SIZE);
+    let generate_input = move |_| {
+        match generate::generate(selected_day.get_untracked(), generate_seed.get_untracked(), generate_size.get_untracked()) {
+            Ok(input) => set_input(input),
+            Err(err) => set_message.set(err.to_string()),
+        }
+    };
+    let no_generator = move || days::find_day(selected_day.get()).is_none_or(|solution| solution.generator.is_none());
+
+    let load_file = move |file: web_sys::File| {
+        leptos::task::spawn_local(async move {
+            match files::read_file(file).await {
+                Ok(text) => set_input(text),
+                Err(err) => set_message.set(err),
+            }
+        });
+    };
+
     let disabled  = move || {
-        let _disabled: bool = process.pending().get();
+        let _disabled: bool = pending.get() || !ready.get();
         if _disabled {
             Some("true")
         } else {
@@ -45,37 +417,245 @@ pub fn Main() -> impl IntoView {
         }
     };
 
+    let history_rows = move || {
+        history.with(|history| history.entries().map(|entry| {
+            let id = entry.id;
+            view! {
+                <tr>
+                    <td>
+                        <input
+                            type="checkbox"
+                            prop:checked={move || compared.with(|compared| compared.contains(&id))}
+                            on:change=move |_| { toggle_compared(id); }
+                        />
+                    </td>
+                    <td>{entry.timestamp.clone()}</td>
+                    <td>{entry.label()}</td>
+                    <td>{entry.input_hash.clone()}</td>
+                    <td>{entry.outcome()}</td>
+                    <td>{format!("{:.3} ms", entry.timings.total_ms())}</td>
+                    <td><button disabled={disabled} on:click=move |_| { rerun(id); }>Re-run</button></td>
+                </tr>
+            }
+        }).collect::<Vec<_>>())
+    };
     view! {
         <div>
-            <h4 style="margin-bottom: 20px;">Input:</h4>
+            <h4 style="margin-bottom: 20px;">"Input for day "{selected_day}":"</h4>
+            <div style="margin-bottom: 10px;">
+                <input
+                    type="file"
+                    on:change:target={
+                        move |e| {
+                            if let Some(file) = e.target().files().and_then(|files| files.get(0)) {
+                                load_file(file);
+                            }
+                        }
+                    }
+                />
+            </div>
+            <div style="display: flex; gap: 10px; margin-bottom: 10px;">
+                <label>
+                    "Seed "
+                    <input
+                        type="number"
+                        min="0"
+                        prop:value={move || generate_seed.get().to_string()}
+                        on:change:target=move |e| {
+                            if let Ok(seed) = e.target().value().parse::<u64>() {
+                                set_generate_seed.set(seed);
+                            }
+                        }
+                    />
+                </label>
+                <label>
+                    "Size "
+                    <input
+                        type="number"
+                        min="1"
+                        prop:value={move || generate_size.get().to_string()}
+                        on:change:target=move |e| {
+                            if let Ok(size) = e.target().value().parse::<usize>() {
+                                set_generate_size.set(size.max(1));
+                            }
+                        }
+                    />
+                </label>
+                <button disabled={no_generator} on:click=generate_input>Generate input</button>
+            </div>
+            {move || {
+                let day = selected_day.get();
+                let declared = days::find_day(day).map_or(&[][..], |solution| solution.params);
+                let inputs = declared.iter().map(|param| {
+                    let (name, default) = (param.name, param.default);
+                    let value = move || param_overrides.with(|overrides| {
+                        overrides.get(&day).and_then(|values| values.iter().find(|(other, _)| other == name)).map_or(
+                            default.to_string(), |(_, value)| value.clone()
+                        )
+                    });
+                    view! {
+                        <label title={param.description}>
+                            {name}" "
+                            <input
+                                type={if param.kind == ParamKind::Int { "number" } else { "text" }}
+                                prop:value={value}
+                                on:change:target=move |e| { set_param(day, name, default, e.target().value()); }
+                            />
+                        </label>
+                    }
+                }).collect::<Vec<_>>();
+                (!declared.is_empty()).then(|| view! {
+                    <div style="display: flex; gap: 10px; margin-bottom: 10px;">
+                        <span>"Parameters:"</span>
+                        {inputs}
+                        <button on:click=move |_| { set_param_overrides.update(|overrides| { overrides.remove(&day); }); }>
+                            Reset
+                        </button>
+                    </div>
+                })
+            }}
             <textarea
+                node_ref=textarea
                 rows={20}
                 prop:value={input_text}
                 style="margin-bottom: 20px;"
+                placeholder="Paste input or drop a file here"
                 on:input:target={
                     move |e| {
-                        set_input_text.set(e.target().value());
+                        set_input(e.target().value());
+                    }
+                }
+                // dragover has to be cancelled for the textarea to accept drops
+                on:dragover=move |e| { e.prevent_default(); }
+                on:drop=move |e| {
+                    if let Some(file) = e.data_transfer().and_then(|data| data.files()).and_then(|files| files.get(0)) {
+                        e.prevent_default();
+                        load_file(file);
                     }
                 }
             />
         </div>
         {move || {
-            days::DAY_PARTS.into_iter().filter(|day| day.part == 1).map(|daypart| {
-                let day = daypart.day;
+            days::DAYS.iter().map(|solution| {
+                let day = solution.day;
+
+                // day 11 has a different example for each part, most days share one
+                let examples: Vec<(u64, &'static str)> = days::PARTS.iter().filter_map(
+                    |part| Some((*part, solution.example(*part)?.input))
+                ).collect();
+                let example_buttons = examples.iter().enumerate().filter(|(idx, (_, input))| {
+                    !examples[..*idx].iter().any(|(_, other)| other == input)
+                }).map(|(_, (part, input))| {
+                    let input = *input;
+                    let label = if examples.iter().all(|(_, other)| *other == input) {
+                        "Load example".to_string()
+                    } else {
+                        format!("Load part {part} example")
+                    };
+                    view! {
+                        <button on:click=move |_| {
+                            select_day(day);
+                            set_input(input.to_string());
+                        }>{label}</button>
+                    }
+                }).collect::<Vec<_>>();
+
+                // parts with more than one variant get a picker and a cross-check button
+                let part_buttons = days::PARTS.iter().map(|&part| {
+                    let variants = solution.variants(part);
+                    let (variant, set_variant) = signal(days::DEFAULT_VARIANT.to_string());
+                    let picker = (variants.len() > 1).then(|| view! {
+                        <select on:change:target=move |e| { set_variant.set(e.target().value()); }>
+                            {variants.iter().map(|name| view! { <option value={*name}>{*name}</option> }).collect::<Vec<_>>()}
+                        </select>
+                        <button disabled={disabled} on:click=move |_| { cross_check(day, part); }>Cross-check</button>
+                    });
+                    view! {
+                        <button disabled={disabled} on:click=move |_| { process(day, part, variant.get_untracked()); }>
+                            "Part "{part}
+                        </button>
+                        {picker}
+                    }
+                }).collect::<Vec<_>>();
 
                 view! {
                     <div style="display: flex; gap: 10px;">
-                        <b>"Day "{day}</b>
-                        <button disabled={disabled} on:click=move |e| { process.dispatch((day, 1)); }>Part 1</button>
-                        <button disabled={disabled} on:click=move |e| { process.dispatch((day, 2)); }>Part 2</button>
+                        <label>
+                            <input
+                                type="radio"
+                                name="day"
+                                prop:checked={move || selected_day.get() == day}
+                                on:change=move |_| { select_day(day); }
+                            />
+                            <b>"Day "{day}</b>
+                        </label>
+                        {part_buttons}
+                        <button disabled={disabled} on:click=move |_| { benchmark(day); }>Bench</button>
+                        {example_buttons}
                     </div>
                 }
             }).collect::<Vec<_>>()
         }}
+        <div style="display: flex; gap: 10px; margin-top: 10px;">
+            <button disabled={move || !pending.get()} on:click=cancel>Cancel</button>
+            <button disabled={disabled} on:click=move |_| { send(Request::VerifyExamples); }>Verify all examples</button>
+            <button disabled={disabled} on:click=run_all>Run all</button>
+            <label>
+                "Bench runs "
+                <input
+                    type="number"
+                    min="1"
+                    prop:value={move || bench_runs.get().to_string()}
+                    on:change:target={
+                        move |e| {
+                            if let Ok(runs) = e.target().value().parse::<usize>() {
+                                set_bench_runs.set(runs.max(1));
+                            }
+                        }
+                    }
+                />
+            </label>
+        </div>
         <h4>Messages</h4>
         <div>
             <pre>{message}</pre>
+            <p style="color: red;" hidden={move || save_error.with(Option::is_none)}>{save_error}</p>
+        </div>
+        {trace_table}
+        <table style="text-align: left;" hidden={move || dashboard.with(Vec::is_empty)}>
+            <thead>
+                <tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th><th>Status</th></tr>
+            </thead>
+            <tbody>{dashboard_rows}</tbody>
+        </table>
+        <div style="display: flex; gap: 10px;">
+            <span>{ledger_status}</span>
+            <button disabled={move || last_solved.with(Option::is_none)} on:click=accept>Accept answer</button>
+            <button on:click=export_ledger>Export ledger</button>
+            <label>
+                "Import ledger "
+                <input
+                    type="file"
+                    accept=".json,application/json"
+                    on:change:target={
+                        move |e| {
+                            if let Some(file) = e.target().files().and_then(|files| files.get(0)) {
+                                import_ledger(file);
+                            }
+                        }
+                    }
+                />
+            </label>
         </div>
+        <h4>History</h4>
+        <table style="text-align: left;">
+            <thead>
+                <tr><th></th><th>When</th><th>Run</th><th>Input</th><th>Answer</th><th>Time</th><th></th></tr>
+            </thead>
+            <tbody>{history_rows}</tbody>
+        </table>
+        <pre>{history_diff}</pre>
+        <button on:click=clear_history>Clear history</button>
         <style>
             ":root {
   color-scheme: light dark;
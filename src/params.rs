@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParamKind {
+    Int,
+    Text,
+}
+
+/// A value a solver would otherwise hard-code, e.g. how many connections day 8
+/// makes. Defaults are what the real puzzle uses
+pub struct Param {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParamValue {
+    Int(i64),
+    Text(String),
+}
+
+/// Parameter values by name, as typed by the user. Anything not overridden
+/// keeps its default
+pub type ParamOverrides = Vec<(String, String)>;
+
+/// Every declared parameter with its value checked against its kind
+#[derive(Debug, Clone)]
+pub struct Params {
+    values: Vec<(&'static str, ParamValue)>,
+}
+
+fn parse_value(param: &Param, value: &str) -> Result<ParamValue, AocError> {
+    match param.kind {
+        ParamKind::Int => value.trim().parse::<i64>().map(ParamValue::Int).map_err(
+            |err| AocError::invalid(format!("parameter {} expects a whole number, got {value:?}: {err}", param.name))
+        ),
+        ParamKind::Text => Ok(ParamValue::Text(value.to_string())),
+    }
+}
+
+impl Params {
+    pub fn resolve(declared: &[Param], overrides: &[(String, String)]) -> Result<Params, AocError> {
+        for (name, _) in overrides {
+            if !declared.iter().any(|param| param.name == name) {
+                let names: Vec<&str> = declared.iter().map(|param| param.name).collect();
+                return Err(AocError::invalid(if names.is_empty() {
+                    format!("unknown parameter {name:?}, this day has none")
+                } else {
+                    format!("unknown parameter {name:?}, expected one of {}", names.join(", "))
+                }));
+            }
+        }
+
+        let values = declared.iter().map(|param| {
+            let value = overrides.iter().rev().find(|(name, _)| name == param.name).map_or(
+                param.default, |(_, value)| value.as_str()
+            );
+            Ok((param.name, parse_value(param, value)?))
+        }).collect::<Result<Vec<_>, AocError>>()?;
+        Ok(Params { values })
+    }
+
+    fn get(&self, name: &str) -> Result<&ParamValue, AocError> {
+        self.values.iter().find(|(param, _)| *param == name).map(|(_, value)| value).ok_or_else(
+            || AocError::invalid(format!("parameter {name:?} is not declared"))
+        )
+    }
+
+    pub fn int(&self, name: &str) -> Result<i64, AocError> {
+        match self.get(name)? {
+            ParamValue::Int(value) => Ok(*value),
+            ParamValue::Text(_) => Err(AocError::invalid(format!("parameter {name:?} is not a number"))),
+        }
+    }
+
+    /// An int parameter used as a count or size, so it can't be negative
+    pub fn count(&self, name: &str) -> Result<usize, AocError> {
+        let value = self.int(name)?;
+        usize::try_from(value).map_err(
+            |_| AocError::invalid(format!("parameter {name:?} can't be negative, got {value}"))
+        )
+    }
+
+    pub fn text(&self, name: &str) -> Result<&str, AocError> {
+        match self.get(name)? {
+            ParamValue::Text(value) => Ok(value),
+            ParamValue::Int(_) => Err(AocError::invalid(format!("parameter {name:?} is not text"))),
+        }
+    }
+}
+
+/// Parses `name=value`, as given on the command line
+pub fn parse_override(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=').map(|(name, value)| (name.to_string(), value.to_string())).ok_or_else(
+        || format!("expected NAME=VALUE, got {arg:?}")
+    )
+}
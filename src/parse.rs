@@ -0,0 +1,77 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::AocError;
+
+/// The lines of `input` with anything in them, ignoring blank ones (e.g. at the end)
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().filter(|line| !line.trim().is_empty())
+}
+
+/// The pieces of `piece` between each `separator`, trimmed. Empty pieces are kept so
+/// reading them reports where the missing value was
+pub fn list(piece: &str, separator: char) -> impl Iterator<Item = &str> {
+    piece.split(separator).map(str::trim)
+}
+
+/// Reads one number, e.g. `int::<u32>(input, piece, "coordinate")`. Like everything
+/// here, `piece` must be a slice of `input`, which is how errors find their line
+/// and column
+pub fn int<T>(input: &str, piece: &str, what: &str) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let piece = piece.trim();
+    if piece.is_empty() {
+        return Err(AocError::parse_at(input, piece, format!("expected {what}, found nothing")));
+    }
+    piece.parse().map_err(|err| AocError::parse_at(input, piece, format!("expected {what}, found {piece:?}: {err}")))
+}
+
+/// Reads numbers separated by `separator`, e.g. "3,5,4,7"
+pub fn ints<T>(input: &str, piece: &str, separator: char, what: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    list(piece, separator).map(|item| int(input, item, what)).collect()
+}
+
+/// Reads exactly `N` numbers separated by `separator`, e.g. the "162,817,812" of a
+/// 3D coordinate
+pub fn int_array<T, const N: usize>(input: &str, piece: &str, separator: char, what: &str) -> Result<[T; N], AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let items: Vec<T> = ints(input, piece, separator, what)?;
+    let found = items.len();
+    items.try_into().map_err(|_| AocError::parse_at(
+        input, piece.trim(), format!("expected {N} values separated by {separator:?}, found {found}")
+    ))
+}
+
+/// Reads an inclusive range written as "a-b", returned as (a, b)
+pub fn range<T>(input: &str, piece: &str, what: &str) -> Result<(T, T), AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let piece = piece.trim();
+    let (start, end) = piece.split_once('-').ok_or_else(
+        || AocError::parse_at(input, piece, format!("expected a range like 3-7, found {piece:?}"))
+    )?;
+    Ok((int(input, start, what)?, int(input, end, what)?))
+}
+
+/// The contents of `piece` between `open` and `close`, e.g. the "3,5,4,7" of "{3,5,4,7}"
+pub fn bracketed<'a>(input: &str, piece: &'a str, open: char, close: char, what: &str) -> Result<&'a str, AocError> {
+    let piece = piece.trim();
+    let inner = piece.strip_prefix(open).ok_or_else(
+        || AocError::parse_at(input, piece, format!("expected {what} starting with {open:?}"))
+    )?;
+    inner.strip_suffix(close).ok_or_else(
+        || AocError::parse_at(input, piece, format!("expected {what} ending with {close:?}"))
+    )
+}
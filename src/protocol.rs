@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+
+use crate::answer::Answer;
+use crate::bench::{self, BenchJob, BenchReport};
+use crate::crosscheck::{self, VariantRun};
+use crate::dashboard::{self, DashboardRow};
+use crate::days::find_daypart;
+use crate::error::AocError;
+use crate::params::ParamOverrides;
+use crate::timing::Timings;
+use crate::verify::{self, Verification};
+
+/// Messages from the page to the solver worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Request {
+    Solve { day: u64, part: u64, variant: String, params: ParamOverrides, input: String },
+    /// Run every variant of a part and compare their answers
+    CrossCheck { day: u64, part: u64, params: ParamOverrides, input: String },
+    Bench { jobs: Vec<BenchJob>, runs: usize },
+    /// Solve every job once, for the dashboard
+    RunAll { jobs: Vec<BenchJob> },
+    VerifyExamples,
+}
+
+/// Messages from the solver worker back to the page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Response {
+    /// Sent once the worker's wasm has loaded, messages posted before this are dropped
+    Ready,
+    Solved { day: u64, part: u64, result: Result<Answer, AocError>, timings: Timings },
+    CrossChecked { day: u64, part: u64, result: Result<Vec<VariantRun>, AocError> },
+    Benched { reports: Vec<BenchReport> },
+    RanAll { rows: Vec<DashboardRow> },
+    Verified { verifications: Vec<Verification> },
+}
+
+/// Messages cross the worker boundary as JSON strings
+pub fn encode<T: Serialize>(message: &T) -> String {
+    serde_json::to_string(message).expect("protocol messages always serialize")
+}
+
+pub fn decode<T: DeserializeOwned>(message: &str) -> Result<T, String> {
+    serde_json::from_str(message).map_err(|err| format!("Could not decode message: {err}"))
+}
+
+pub fn handle(request: Request) -> Response {
+    match request {
+        Request::Solve { day, part, variant, params, input } => {
+            let (result, timings) = match find_daypart(day, part) {
+                Some(daypart) => daypart.run_variant(&input, &variant, &params),
+                None => (
+                    Err(AocError::no_solution(format!("No function found for day {day} part {part}"))),
+                    Timings { parse_ms: 0.0, solve_ms: 0.0 },
+                ),
+            };
+            Response::Solved { day, part, result, timings }
+        }
+        Request::CrossCheck { day, part, params, input } => {
+            Response::CrossChecked { day, part, result: crosscheck::cross_check(day, part, &input, &params) }
+        }
+        Request::Bench { jobs, runs } => Response::Benched { reports: bench::bench_all(&jobs, runs) },
+        Request::RunAll { jobs } => Response::RanAll { rows: dashboard::run_all(&jobs) },
+        Request::VerifyExamples => Response::Verified { verifications: verify::verify_examples() },
+    }
+}
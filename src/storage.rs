@@ -0,0 +1,62 @@
+use web_sys::Storage;
+
+use crate::history::History;
+use crate::ledger::Ledger;
+
+/// The browser's local storage, or None where it's unavailable (e.g. some private
+/// browsing modes), in which case nothing persists across reloads
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Saves `value` under `key`, failing when the browser refuses it, usually because
+/// local storage is full. Without local storage there's nothing to save to, which
+/// isn't an error
+fn save(key: &str, value: &str, what: &str) -> Result<(), String> {
+    match local_storage() {
+        Some(storage) => storage.set_item(key, value).map_err(|err| format!("Could not save {what}: {err:?}")),
+        None => Ok(()),
+    }
+}
+
+fn input_key(day: u64) -> String {
+    format!("aoc2025.input.day{day}")
+}
+
+pub fn load_input(day: u64) -> Option<String> {
+    local_storage()?.get_item(&input_key(day)).ok().flatten()
+}
+
+pub fn save_input(day: u64, input: &str) -> Result<(), String> {
+    save(&input_key(day), input, &format!("the input for day {day}"))
+}
+
+pub fn load_selected_day() -> Option<u64> {
+    local_storage()?.get_item("aoc2025.selected_day").ok().flatten()?.parse().ok()
+}
+
+pub fn save_selected_day(day: u64) -> Result<(), String> {
+    save("aoc2025.selected_day", &day.to_string(), "the selected day")
+}
+
+/// The browser's copy of the ledger, empty if there isn't one or it can't be read
+pub fn load_ledger() -> Ledger {
+    local_storage().and_then(|storage| storage.get_item("aoc2025.ledger").ok().flatten()).and_then(
+        |json| Ledger::from_json(&json).ok()
+    ).unwrap_or_default()
+}
+
+pub fn save_ledger(ledger: &Ledger) -> Result<(), String> {
+    save("aoc2025.ledger", &ledger.to_json(), "the ledger")
+}
+
+/// Previous runs, empty if there aren't any or they can't be read
+pub fn load_history() -> History {
+    local_storage().and_then(|storage| storage.get_item("aoc2025.history").ok().flatten()).and_then(
+        |json| History::from_json(&json).ok()
+    ).unwrap_or_default()
+}
+
+pub fn save_history(history: &History) -> Result<(), String> {
+    save("aoc2025.history", &history.to_json(), "the history")
+}
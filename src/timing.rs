@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds since an arbitrary starting point. `Instant` isn't available on
+/// wasm32-unknown-unknown, so in the browser this reads `performance.now()`
+#[cfg(target_arch = "wasm32")]
+pub fn now_ms() -> f64 {
+    use leptos::wasm_bindgen::JsCast;
+    use web_sys::js_sys;
+
+    // works on both the window and worker global scopes
+    js_sys::Reflect::get(&js_sys::global(), &"performance".into())
+        .map(|performance| performance.unchecked_into::<web_sys::Performance>().now())
+        .unwrap_or(0.0)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_ms() -> f64 {
+    static EPOCH: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    EPOCH.get_or_init(std::time::Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+}
+
+impl Timings {
+    pub fn total_ms(&self) -> f64 {
+        self.parse_ms + self.solve_ms
+    }
+}
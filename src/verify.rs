@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::days::{catching_panics, day_parts, DEFAULT_VARIANT};
+use crate::error::AocError;
+
+/// The outcome of running one solver on its puzzle example
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verification {
+    pub day: u64,
+    pub part: u64,
+    pub variant: String,
+    pub expected: String,
+    pub result: Result<Answer, AocError>,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        matches!(&self.result, Ok(answer) if answer.to_string() == self.expected)
+    }
+}
+
+/// Runs every variant of every solver which has an example, skipping the rest
+pub fn verify_examples() -> Vec<Verification> {
+    day_parts().filter_map(|daypart| Some((daypart, daypart.example?))).flat_map(|(daypart, example)| {
+        daypart.variants().into_iter().map(move |variant| {
+            let (result, _) = catching_panics(|| daypart.run_variant(example.input, variant, &example.overrides()));
+            Verification {
+                day: daypart.day,
+                part: daypart.part,
+                variant: variant.to_string(),
+                expected: example.answer.to_string(),
+                result,
+            }
+        })
+    }).collect()
+}
+
+pub fn format_verifications(verifications: &[Verification]) -> String {
+    let mut text = String::new();
+    for verification in verifications {
+        let status = match &verification.result {
+            _ if verification.passed() => "pass".to_string(),
+            Ok(answer) => format!("FAIL, expected {} but got {answer}", verification.expected),
+            Err(err) => format!("FAIL, {err}"),
+        };
+        let variant = if verification.variant == DEFAULT_VARIANT {
+            String::new()
+        } else {
+            format!(" ({})", verification.variant)
+        };
+        text.push_str(&format!("day {} part {}{variant}: {status}\n", verification.day, verification.part));
+    }
+    let passed = verifications.iter().filter(|verification| verification.passed()).count();
+    text.push_str(&format!("{passed} of {} examples pass\n", verifications.len()));
+    text
+}
@@ -0,0 +1,41 @@
+//! Runs every part that has more than one variant on small generated inputs and
+//! fails with the smallest input the variants disagree on. The default run keeps
+//! the slow references small; `cargo test -- --ignored` runs the full sweep
+
+use aoc2025::days::day_parts;
+use aoc2025::difftest::{find_mismatch, format_mismatch};
+
+/// Seeds tried at each size
+const SEEDS: u64 = 20;
+
+/// Largest generated size in the full sweep
+const MAX_SIZE: usize = 12;
+
+/// Largest generated size by default for days whose reference tries every id or
+/// every press count, which take seconds per size in debug builds
+const SLOW_MAX_SIZE: usize = 6;
+
+const SLOW_DAYS: [u64; 2] = [2, 10];
+
+fn sweep(max_size: impl Fn(u64) -> usize) {
+    let mut failures = Vec::new();
+    for daypart in day_parts().filter(|daypart| daypart.variants().len() > 1) {
+        match find_mismatch(daypart.day, daypart.part, 0..SEEDS, max_size(daypart.day)) {
+            Ok(None) => {}
+            Ok(Some(mismatch)) => failures.push(format_mismatch(&mismatch)),
+            Err(err) => failures.push(format!("Day {} part {}: {err}", daypart.day, daypart.part)),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn variants_agree_on_generated_inputs() {
+    sweep(|day| if SLOW_DAYS.contains(&day) { SLOW_MAX_SIZE } else { MAX_SIZE });
+}
+
+#[test]
+#[ignore = "takes about a minute in debug builds"]
+fn variants_agree_on_every_generated_size() {
+    sweep(|_| MAX_SIZE);
+}